@@ -0,0 +1,207 @@
+use std::{collections::HashSet, env, fs, process};
+
+use pathfinder::{
+    map::tile_char, parse_map, path_cost, pathfind, Algorithm, Grid, Options, SearchResult,
+};
+
+const USAGE: &str = "\
+Usage: pathfind-cli <map file> [options]
+
+Options:
+  --algorithm <name>      dijkstra (default), astar, aggressive-star, bfs, dfs, jps, dstar-lite
+  --start <row,col>       where to search from, defaults to the map's B, then P tile, or 0,0
+  --no-wrap               don't world wrap
+  --bidirectional         search from both ends (dijkstra, astar, aggressive-star, bfs)
+  --all-goals             walk to every reachable E in the cheapest order, not just the nearest
+  --direction-offset <n>  rotate the direction bias, 0-7
+  --random-direction      shuffle the direction bias
+  --seed <n>              seed for --random-direction, defaults to 0
+  --render                print the grid with the explored tiles and path
+";
+
+struct Args {
+    map_path: String,
+    options: Options,
+    start: Option<(usize, usize)>,
+    render: bool,
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) if message.is_empty() => {
+            print!("{USAGE}");
+            return;
+        }
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let text = fs::read_to_string(&args.map_path).unwrap_or_else(|err| {
+        eprintln!("couldn't read {}: {err}", args.map_path);
+        process::exit(1);
+    });
+    let map = parse_map(&text).unwrap_or_else(|err| {
+        eprintln!("couldn't parse {}: {err}", args.map_path);
+        process::exit(1);
+    });
+
+    let start = args
+        .start
+        .or(map.grid.start())
+        .or(map.start)
+        .unwrap_or((0, 0));
+    if start.0 >= map.grid.rows() || start.1 >= map.grid.cols() {
+        eprintln!(
+            "start {},{} is outside the {}x{} map",
+            start.0,
+            start.1,
+            map.grid.rows(),
+            map.grid.cols()
+        );
+        process::exit(1);
+    }
+
+    let result = pathfind(&map.grid, start, &args.options);
+
+    println!("algorithm: {:?}", args.options.algorithm);
+    println!("start: {},{}", start.0, start.1);
+    let ends: Vec<String> = map
+        .grid
+        .ends()
+        .iter()
+        .map(|(row, col)| format!("{row},{col}"))
+        .collect();
+    if ends.is_empty() {
+        println!("end: none");
+    } else {
+        println!("end: {}", ends.join(" "));
+    }
+    println!("visited: {}", result.visited.len());
+    let opened: usize = result.visited.iter().map(|node| node.opened.len()).sum();
+    println!("frontier pushes: {opened}");
+    if result.path.is_empty() {
+        println!("path: none");
+    } else {
+        println!("path length: {}", result.path.len());
+        println!("path cost: {}", path_cost(&map.grid, &result.path));
+        let steps: Vec<String> = result
+            .path
+            .iter()
+            .map(|(row, col)| format!("{row},{col}"))
+            .collect();
+        println!("path: {}", steps.join(" "));
+    }
+
+    if args.render {
+        println!();
+        print!("{}", render(&map.grid, start, &result));
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut map_path = None;
+    let mut options = Options::default();
+    let mut start = None;
+    let mut render = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--algorithm" => {
+                let name = args.next().ok_or("--algorithm needs a name")?;
+                options.algorithm = parse_algorithm(&name)?;
+            }
+            "--start" => {
+                let value = args.next().ok_or("--start needs row,col")?;
+                start = Some(parse_pos(&value)?);
+            }
+            "--no-wrap" => options.world_wrap_enabled = false,
+            "--bidirectional" => options.bidirectional = true,
+            "--all-goals" => options.visit_all_goals = true,
+            "--direction-offset" => {
+                let value = args.next().ok_or("--direction-offset needs a number")?;
+                let offset: usize = value
+                    .parse()
+                    .map_err(|_| format!("bad direction offset `{value}`"))?;
+                options.direction_offset = offset % 8;
+            }
+            "--random-direction" => options.random_direction = true,
+            "--seed" => {
+                let value = args.next().ok_or("--seed needs a number")?;
+                options.seed = value.parse().map_err(|_| format!("bad seed `{value}`"))?;
+            }
+            "--render" => render = true,
+            "-h" | "--help" => return Err("".into()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option `{arg}`")),
+            _ if map_path.is_none() => map_path = Some(arg),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(Args {
+        map_path: map_path.ok_or("missing map file")?,
+        options,
+        start,
+        render,
+    })
+}
+
+fn parse_algorithm(name: &str) -> Result<Algorithm, String> {
+    match name.to_lowercase().as_str() {
+        "dijkstra" => Ok(Algorithm::Dijkstra),
+        "astar" | "a*" => Ok(Algorithm::AStar),
+        "aggressive-star" | "aggressivestar" => Ok(Algorithm::AggressiveStar),
+        "bfs" => Ok(Algorithm::BFS),
+        "dfs" => Ok(Algorithm::DFS),
+        "dstar-lite" | "dstarlite" | "d*-lite" => Ok(Algorithm::DStarLite),
+        "jps" | "jump-point-search" => Ok(Algorithm::JumpPointSearch),
+        _ => Err(format!("unknown algorithm `{name}`")),
+    }
+}
+
+fn parse_pos(value: &str) -> Result<(usize, usize), String> {
+    let (row, col) = value
+        .split_once(',')
+        .ok_or(format!("expected row,col, got `{value}`"))?;
+    let row = row.trim().parse().map_err(|_| format!("bad row `{row}`"))?;
+    let col = col.trim().parse().map_err(|_| format!("bad col `{col}`"))?;
+    Ok((row, col))
+}
+
+// Map characters, with `*` for the path, `o` for explored tiles, `+` for tiles left on the
+// frontier and `P` for the start
+fn render(grid: &Grid, start: (usize, usize), result: &SearchResult) -> String {
+    let visited: HashSet<(usize, usize)> = result.visited.iter().map(|node| node.pos).collect();
+    let frontier: HashSet<(usize, usize)> = result
+        .visited
+        .iter()
+        .flat_map(|node| node.opened.iter().copied())
+        .filter(|pos| !visited.contains(pos))
+        .collect();
+    let path: HashSet<(usize, usize)> = result.path.iter().copied().collect();
+
+    let mut text = String::new();
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            let pos = (row, col);
+            let tile = tile_char(grid.tile_type(pos));
+            text.push(if pos == start {
+                'P'
+            } else if tile == 'E' || tile == 'B' {
+                tile
+            } else if path.contains(&pos) {
+                '*'
+            } else if visited.contains(&pos) {
+                'o'
+            } else if frontier.contains(&pos) {
+                '+'
+            } else {
+                tile
+            });
+        }
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,130 @@
+// Cost of stepping straight onto a tile, diagonal steps cost 1.4x that
+pub const ROAD_COST: usize = 5;
+pub const OPEN_COST: usize = 10;
+pub const MUD_COST: usize = 30;
+pub const WATER_COST: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TileType {
+    Open,
+    End,
+    // Where searches can start from instead of the player, there's only ever one
+    Start,
+    Wall,
+    Road,
+    Mud,
+    Water,
+}
+
+impl TileType {
+    pub fn cost(&self) -> usize {
+        match self {
+            TileType::Road => ROAD_COST,
+            TileType::Mud => MUD_COST,
+            TileType::Water => WATER_COST,
+            TileType::Open | TileType::End | TileType::Start | TileType::Wall => OPEN_COST,
+        }
+    }
+}
+
+/*
+ * Everything the algorithms get to know about the world: how big it is and what every tile is.
+ * Positions are (row, col) like everywhere else, row 0 is the top.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    rows: usize,
+    cols: usize,
+    tiles: Vec<TileType>,
+}
+
+impl Grid {
+    pub fn new(rows: usize, cols: usize) -> Grid {
+        Grid {
+            rows,
+            cols,
+            tiles: vec![TileType::Open; rows * cols],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn tile_type(&self, (row, col): (usize, usize)) -> TileType {
+        self.tiles[row * self.cols + col]
+    }
+
+    pub fn set_tile_type(&mut self, (row, col): (usize, usize), tile_type: TileType) {
+        self.tiles[row * self.cols + col] = tile_type;
+    }
+
+    pub fn is_wall(&self, pos: (usize, usize)) -> bool {
+        self.tile_type(pos) == TileType::Wall
+    }
+
+    pub fn cost(&self, pos: (usize, usize)) -> usize {
+        self.tile_type(pos).cost()
+    }
+
+    // Cheapest tile anything can step onto, what a heuristic can scale distance by and still
+    // never overshoot
+    pub fn min_cost(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|&&tile_type| tile_type != TileType::Wall)
+            .map(TileType::cost)
+            .min()
+            .unwrap_or(OPEN_COST)
+    }
+
+    pub fn is_end(&self, pos: (usize, usize)) -> bool {
+        self.tile_type(pos) == TileType::End
+    }
+
+    pub fn start(&self) -> Option<(usize, usize)> {
+        self.positions()
+            .find(|&pos| self.tile_type(pos) == TileType::Start)
+    }
+
+    // There can be any number of them, searches stop at whichever they reach first
+    pub fn ends(&self) -> Vec<(usize, usize)> {
+        self.positions().filter(|&pos| self.is_end(pos)).collect()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows * self.cols).map(move |index| (index / cols, index % cols))
+    }
+
+    pub fn in_bounds(&self, row: isize, col: isize) -> bool {
+        row >= 0 && row < self.rows as isize && col >= 0 && col < self.cols as isize
+    }
+
+    // Where you end up stepping from `pos` by `(dr, dc)`, None if that walks off a bounded grid
+    pub fn step(
+        &self,
+        (row, col): (usize, usize),
+        (dr, dc): (isize, isize),
+        world_wrap_enabled: bool,
+    ) -> Option<(usize, usize)> {
+        if world_wrap_enabled {
+            // add row count to avoid negative index >.> <.<
+            let new_row = ((row + self.rows) as isize + dr) as usize % self.rows;
+            let new_col = ((col + self.cols) as isize + dc) as usize % self.cols;
+            Some((new_row, new_col))
+        } else {
+            let new_row = row as isize + dr;
+            let new_col = col as isize + dc;
+            if self.in_bounds(new_row, new_col) {
+                Some((new_row as usize, new_col as usize))
+            } else {
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// The algorithms without the game, nothing in here knows about Bevy. It's its own crate so it
+// builds and tests on machines without a window or audio. The plugins over in the game's main.rs
+// turn tiles into a `Grid` and results back into events.
+
+pub mod grid;
+pub mod map;
+pub mod pathfinding {
+    pub mod algorithms;
+}
+pub mod terrain {
+    pub mod algorithms;
+}
+
+pub use grid::{Grid, TileType};
+pub use map::{parse_map, write_map, Map};
+pub use pathfinding::algorithms::{path_cost, pathfind, Algorithm, Options, SearchResult};
+pub use terrain::algorithms::{generate_maze, TerrainAlgorithm, TerrainSettings};
@@ -0,0 +1,286 @@
+use std::fmt;
+
+use crate::grid::{Grid, TileType};
+
+/*
+ * Plain text maps, close enough to the Moving AI `.map` benchmark format that those load too.
+ *
+ * type weighted
+ * height 3
+ * width 4
+ * map
+ * P..@
+ * .@@.
+ * ...E
+ *
+ * `.` or `G` open, `@`, `O` or `T` wall, `E` end, `B` start tile (begin), `P` where the player
+ * stands (open underneath), `R` road, `S` mud (swamp), `W` water. The header is optional, rows
+ * alone are fine.
+ *
+ * Moving AI maps say `type octile`, and there `W` is water nothing on land can cross, so it loads
+ * as a wall instead.
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Map {
+    pub grid: Grid,
+    pub start: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapError {
+    Empty,
+    BadHeader(String),
+    UnknownTile {
+        row: usize,
+        col: usize,
+        tile: char,
+    },
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    RowCount {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Empty => write!(f, "map has no rows"),
+            MapError::BadHeader(line) => write!(f, "couldn't read header line `{line}`"),
+            MapError::UnknownTile { row, col, tile } => {
+                write!(f, "unknown tile `{tile}` at row {row}, col {col}")
+            }
+            MapError::RowLength {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} is {found} tiles wide, expected {expected}"),
+            MapError::RowCount { expected, found } => {
+                write!(f, "map has {found} rows, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+pub fn tile_char(tile_type: TileType) -> char {
+    match tile_type {
+        TileType::Open => '.',
+        TileType::Wall => '@',
+        TileType::End => 'E',
+        TileType::Start => 'B',
+        TileType::Road => 'R',
+        TileType::Mud => 'S',
+        TileType::Water => 'W',
+    }
+}
+
+// What `W` is for this repo's own maps, Moving AI ones wall it off in `parse_map`
+fn tile_from_char(tile: char) -> Option<TileType> {
+    match tile {
+        '.' | 'G' | 'P' => Some(TileType::Open),
+        '@' | 'O' | 'T' => Some(TileType::Wall),
+        'E' => Some(TileType::End),
+        'B' => Some(TileType::Start),
+        'R' => Some(TileType::Road),
+        'S' => Some(TileType::Mud),
+        'W' => Some(TileType::Water),
+        _ => None,
+    }
+}
+
+pub fn parse_map(text: &str) -> Result<Map, MapError> {
+    let mut lines = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty());
+    let mut height: Option<usize> = None;
+    let mut width: Option<usize> = None;
+    let mut rows: Vec<&str> = vec![];
+    let mut moving_ai = false;
+
+    let mut peeked = lines.next();
+    if let Some(type_line) = peeked.filter(|line| line.starts_with("type")) {
+        moving_ai = type_line.split_whitespace().nth(1) == Some("octile");
+        for line in lines.by_ref() {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some("map"), None) => break,
+                (Some("height"), Some(value)) => {
+                    height = Some(
+                        value
+                            .parse()
+                            .map_err(|_| MapError::BadHeader(line.into()))?,
+                    )
+                }
+                (Some("width"), Some(value)) => {
+                    width = Some(
+                        value
+                            .parse()
+                            .map_err(|_| MapError::BadHeader(line.into()))?,
+                    )
+                }
+                _ => return Err(MapError::BadHeader(line.into())),
+            }
+        }
+        peeked = lines.next();
+    }
+    rows.extend(peeked);
+    rows.extend(lines);
+
+    if rows.is_empty() {
+        return Err(MapError::Empty);
+    }
+    let row_count = height.unwrap_or(rows.len());
+    let col_count = width.unwrap_or(rows[0].chars().count());
+    if rows.len() != row_count {
+        return Err(MapError::RowCount {
+            expected: row_count,
+            found: rows.len(),
+        });
+    }
+
+    let mut grid = Grid::new(row_count, col_count);
+    let mut start = None;
+    for (row, line) in rows.iter().enumerate() {
+        let found = line.chars().count();
+        if found != col_count {
+            return Err(MapError::RowLength {
+                row,
+                expected: col_count,
+                found,
+            });
+        }
+        for (col, tile) in line.chars().enumerate() {
+            let tile_type = match tile {
+                'W' if moving_ai => TileType::Wall,
+                _ => tile_from_char(tile).ok_or(MapError::UnknownTile { row, col, tile })?,
+            };
+            if tile == 'P' {
+                start = Some((row, col));
+            }
+            grid.set_tile_type((row, col), tile_type);
+        }
+    }
+
+    Ok(Map { grid, start })
+}
+
+pub fn write_map(map: &Map) -> String {
+    let grid = &map.grid;
+    let mut text = format!(
+        "type weighted\nheight {}\nwidth {}\nmap\n",
+        grid.rows(),
+        grid.cols()
+    );
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            let tile_type = grid.tile_type((row, col));
+            if map.start == Some((row, col)) && tile_type == TileType::Open {
+                text.push('P');
+            } else {
+                text.push(tile_char(tile_type));
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_then_parse_gives_the_same_map() {
+        let mut grid = Grid::new(3, 4);
+        let tiles = [
+            TileType::Open,
+            TileType::Wall,
+            TileType::End,
+            TileType::Start,
+            TileType::Road,
+            TileType::Mud,
+            TileType::Water,
+        ];
+        for (i, pos) in grid.positions().collect::<Vec<_>>().into_iter().enumerate() {
+            grid.set_tile_type(pos, tiles[i % tiles.len()]);
+        }
+        let map = Map {
+            grid,
+            start: Some((1, 3)),
+        };
+        let text = write_map(&map);
+        assert!(text.starts_with("type weighted\nheight 3\nwidth 4\nmap\n"));
+        assert_eq!(parse_map(&text), Ok(map));
+    }
+
+    #[test]
+    fn rows_alone_are_a_map() {
+        let map = parse_map("P.@\n..E\n").unwrap();
+        assert_eq!(map.start, Some((0, 0)));
+        assert_eq!(map.grid.rows(), 2);
+        assert_eq!(map.grid.cols(), 3);
+        assert!(map.grid.is_wall((0, 2)));
+        assert!(map.grid.is_end((1, 2)));
+    }
+
+    #[test]
+    fn moving_ai_water_is_a_wall() {
+        let moving_ai = parse_map("type octile\nheight 1\nwidth 3\nmap\n.WT\n").unwrap();
+        assert!(moving_ai.grid.is_wall((0, 1)));
+        assert!(moving_ai.grid.is_wall((0, 2)));
+        let ours = parse_map("type weighted\nheight 1\nwidth 3\nmap\n.WT\n").unwrap();
+        assert_eq!(ours.grid.tile_type((0, 1)), TileType::Water);
+    }
+
+    #[test]
+    fn header_has_to_match_the_rows() {
+        assert_eq!(
+            parse_map("type octile\nheight 3\nwidth 2\nmap\n..\n..\n"),
+            Err(MapError::RowCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            parse_map("type octile\nheight 2\nwidth 3\nmap\n...\n..\n"),
+            Err(MapError::RowLength {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            parse_map("type octile\nheight two\n"),
+            Err(MapError::BadHeader("height two".into()))
+        );
+    }
+
+    #[test]
+    fn bad_rows_are_errors() {
+        assert_eq!(parse_map("\n\n"), Err(MapError::Empty));
+        assert_eq!(
+            parse_map("...\n..\n"),
+            Err(MapError::RowLength {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            parse_map("..\n.x\n"),
+            Err(MapError::UnknownTile {
+                row: 1,
+                col: 1,
+                tile: 'x'
+            })
+        );
+    }
+}
@@ -0,0 +1,480 @@
+use crate::grid::Grid;
+
+pub mod astar;
+pub mod bfs;
+pub mod bidirectional;
+pub mod cooperative;
+pub mod dfs;
+pub mod dijkstra;
+pub mod distance_field;
+pub mod dstar_lite;
+pub mod flow_field;
+pub mod jps;
+pub mod node;
+pub mod tour;
+mod util;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    AggressiveStar,
+    AStar,
+    BFS,
+    DFS,
+    Dijkstra,
+    // Searches back from the ends, `dstar_lite::DStarLite` keeps it around to replan cheaply
+    DStarLite,
+    JumpPointSearch,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub algorithm: Algorithm,
+    pub direction_offset: usize,
+    pub random_direction: bool,
+    pub world_wrap_enabled: bool,
+    pub bidirectional: bool,
+    // Drives the random direction shuffle, same seed same search
+    pub seed: u64,
+    // Search from the Start tile when there is one instead of the position passed in
+    pub use_start_tile: bool,
+    // Keep going after the first end until every reachable end has been walked to
+    pub visit_all_goals: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            algorithm: Algorithm::Dijkstra,
+            direction_offset: 0,
+            random_direction: false,
+            world_wrap_enabled: true,
+            bidirectional: false,
+            seed: 0,
+            use_start_tile: false,
+            visit_all_goals: false,
+        }
+    }
+}
+
+// Which end of a bidirectional search expanded the node, everything else is `Start`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchSide {
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchNode {
+    pub pos: (usize, usize),
+    pub side: SearchSide,
+    // Cost from wherever its side started when it was expanded, hops for BFS and DFS
+    pub g_score: usize,
+    // Parent in the search tree when it was expanded, None for wherever its side started
+    pub previous_node: Option<(usize, usize)>,
+    // Tiles that went onto the frontier (heap or queue) while this one was being expanded
+    pub opened: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchResult {
+    // In the order the algorithm expanded (closed) them
+    pub visited: Vec<SearchNode>,
+    // Walking order from the start to the end, empty if the end wasn't reached
+    pub path: Vec<(usize, usize)>,
+}
+
+// Stops at whichever end it reaches first, unless `visit_all_goals` wants the whole tour
+pub fn pathfind(grid: &Grid, start: (usize, usize), options: &Options) -> SearchResult {
+    let start = search_start(grid, start, options);
+    if options.visit_all_goals && grid.ends().len() > 1 {
+        return tour::tour(grid, start, options);
+    }
+    if options.bidirectional {
+        match options.algorithm {
+            Algorithm::AStar | Algorithm::AggressiveStar | Algorithm::BFS | Algorithm::Dijkstra => {
+                return bidirectional::bidirectional(grid, start, options)
+            }
+            _ => {}
+        }
+    }
+    match options.algorithm {
+        Algorithm::AStar => astar::astar(grid, start, false, options),
+        Algorithm::AggressiveStar => astar::astar(grid, start, true, options),
+        Algorithm::BFS => bfs::bfs(grid, start, options),
+        Algorithm::DFS => dfs::dfs(grid, start, options),
+        Algorithm::Dijkstra => dijkstra::dijkstra(grid, start, options),
+        Algorithm::DStarLite => dstar_lite::DStarLite::new(grid, start, options).search(),
+        Algorithm::JumpPointSearch => jps::jps(grid, start, options),
+    }
+}
+
+// The Start tile when the options want it and there is one, otherwise `start`
+pub fn search_start(grid: &Grid, start: (usize, usize), options: &Options) -> (usize, usize) {
+    match grid.start() {
+        Some(start_tile) if options.use_start_tile => start_tile,
+        _ => start,
+    }
+}
+
+// What walking the path costs, the same step costs Dijkstra and A* pay
+pub fn path_cost(grid: &Grid, path: &[(usize, usize)]) -> usize {
+    path.windows(2)
+        .map(|step| {
+            let ((from_row, from_col), to) = (step[0], step[1]);
+            let dr = (from_row != to.0) as isize;
+            let dc = (from_col != to.1) as isize;
+            util::step_cost(grid.cost(to), (dr, dc))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{TileType, OPEN_COST, ROAD_COST};
+    use crate::map::parse_map;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::VecDeque;
+
+    const ALL: [Algorithm; 7] = [
+        Algorithm::AggressiveStar,
+        Algorithm::AStar,
+        Algorithm::BFS,
+        Algorithm::DFS,
+        Algorithm::Dijkstra,
+        Algorithm::DStarLite,
+        Algorithm::JumpPointSearch,
+    ];
+
+    fn grid(rows: &str) -> Grid {
+        parse_map(rows).unwrap().grid
+    }
+
+    fn options(algorithm: Algorithm) -> Options {
+        Options {
+            algorithm,
+            world_wrap_enabled: false,
+            ..Options::default()
+        }
+    }
+
+    // Starts at the start, ends on an end, every step is to a neighbor that isn't a wall
+    fn assert_walkable(grid: &Grid, start: (usize, usize), path: &[(usize, usize)]) {
+        assert_eq!(path.first(), Some(&start));
+        assert!(grid.is_end(*path.last().unwrap()));
+        for step in path.windows(2) {
+            assert!(step[0].0.abs_diff(step[1].0) <= 1 && step[0].1.abs_diff(step[1].1) <= 1);
+            assert_ne!(step[0], step[1]);
+            assert!(!grid.is_wall(step[1]));
+        }
+    }
+
+    #[test]
+    fn every_algorithm_gets_around_a_wall() {
+        let grid = grid(
+            "\
+.....
+.@@@.
+.@E@.
+.@.@.
+.....",
+        );
+        for algorithm in ALL {
+            let result = pathfind(&grid, (0, 0), &options(algorithm));
+            assert_walkable(&grid, (0, 0), &result.path);
+            assert!(!result.visited.is_empty(), "{algorithm:?}");
+            assert!(
+                result.visited.iter().any(|node| !node.opened.is_empty()),
+                "{algorithm:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_algorithm_stops_on_an_end_it_starts_on() {
+        let grid = grid(
+            "\
+...
+.E.
+...",
+        );
+        for algorithm in ALL {
+            for bidirectional in [false, true] {
+                let options = Options {
+                    bidirectional,
+                    ..options(algorithm)
+                };
+                let path = pathfind(&grid, (1, 1), &options).path;
+                assert_eq!(
+                    path,
+                    vec![(1, 1)],
+                    "{algorithm:?}, bidirectional {bidirectional}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_finds_a_walled_off_end() {
+        let grid = grid(
+            "\
+.....
+.@@@.
+.@E@.
+.@@@.
+.....",
+        );
+        for algorithm in ALL {
+            assert!(
+                pathfind(&grid, (0, 0), &options(algorithm)).path.is_empty(),
+                "{algorithm:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn cost_aware_searches_take_the_road() {
+        // Straight through the mud is shorter, around on the road is cheaper
+        let grid = grid(
+            "\
+.RRRRR.
+.SSSSSE
+.......",
+        );
+        let dijkstra = pathfind(&grid, (1, 0), &options(Algorithm::Dijkstra)).path;
+        let cheapest = path_cost(&grid, &dijkstra);
+        assert!(dijkstra.contains(&(0, 3)));
+        for algorithm in [Algorithm::AStar, Algorithm::DStarLite] {
+            let path = pathfind(&grid, (1, 0), &options(algorithm)).path;
+            assert_walkable(&grid, (1, 0), &path);
+            assert_eq!(path_cost(&grid, &path), cheapest, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn astar_on_open_ground_heads_straight_for_the_end() {
+        // No road anywhere, so the estimate can count every step at open ground's cost
+        let mut grid = Grid::new(15, 15);
+        grid.set_tile_type((7, 14), TileType::End);
+        assert_eq!(grid.min_cost(), OPEN_COST);
+        let astar = pathfind(&grid, (7, 0), &options(Algorithm::AStar));
+        let dijkstra = pathfind(&grid, (7, 0), &options(Algorithm::Dijkstra));
+        assert_eq!(
+            path_cost(&grid, &astar.path),
+            path_cost(&grid, &dijkstra.path)
+        );
+        assert!(astar.visited.len() <= astar.path.len());
+
+        grid.set_tile_type((0, 0), TileType::Road);
+        assert_eq!(grid.min_cost(), ROAD_COST);
+    }
+
+    #[test]
+    fn bfs_and_jps_take_the_fewest_steps() {
+        let grid = grid(
+            "\
+..........
+.@@@@@@@@.
+.........E
+@@@@@@@@..
+..........",
+        );
+        let dijkstra = pathfind(&grid, (4, 0), &options(Algorithm::Dijkstra)).path;
+        for algorithm in [Algorithm::BFS, Algorithm::JumpPointSearch] {
+            let path = pathfind(&grid, (4, 0), &options(algorithm)).path;
+            assert_walkable(&grid, (4, 0), &path);
+            assert_eq!(path.len(), dijkstra.len(), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn bfs_goes_straight_to_an_end_next_door() {
+        // The start queues the End first, tiles a hop further out queue it again. Whichever got
+        // there last being its parent walked there the long way round
+        let grid = grid(
+            "\
+@.E
+...",
+        );
+        let path = pathfind(&grid, (1, 2), &options(Algorithm::BFS)).path;
+        assert_eq!(path, vec![(1, 2), (0, 2)]);
+    }
+
+    #[test]
+    fn bfs_parents_are_one_hop_closer() {
+        // What the heatmap shows and where the tree arrows point have to agree
+        let grid = grid(
+            "\
+.....@....
+.@@..@.@@.
+.@...@..@.
+.@.@@@@.@E
+...@......",
+        );
+        for world_wrap_enabled in [false, true] {
+            let options = Options {
+                world_wrap_enabled,
+                ..options(Algorithm::BFS)
+            };
+            let visited = pathfind(&grid, (0, 0), &options).visited;
+            for node in &visited {
+                let Some(previous) = node.previous_node else {
+                    assert_eq!(node.g_score, 0);
+                    continue;
+                };
+                let parent = visited
+                    .iter()
+                    .find(|parent| parent.pos == previous)
+                    .unwrap();
+                assert_eq!(node.g_score, parent.g_score + 1, "{:?}", node.pos);
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_matches_one_way() {
+        let grid = grid(
+            "\
+...S.....
+.@@S@@@@.
+...S..@.E
+.@@@@.@..
+.........",
+        );
+        for algorithm in [
+            Algorithm::AStar,
+            Algorithm::AggressiveStar,
+            Algorithm::BFS,
+            Algorithm::Dijkstra,
+        ] {
+            let one_way = pathfind(&grid, (0, 0), &options(algorithm)).path;
+            let both_ways = Options {
+                bidirectional: true,
+                ..options(algorithm)
+            };
+            let result = pathfind(&grid, (0, 0), &both_ways);
+            assert_walkable(&grid, (0, 0), &result.path);
+            assert!(result
+                .visited
+                .iter()
+                .any(|node| node.side == SearchSide::End));
+            if algorithm == Algorithm::BFS {
+                assert_eq!(result.path.len(), one_way.len());
+            } else if algorithm != Algorithm::AggressiveStar {
+                assert_eq!(
+                    path_cost(&grid, &result.path),
+                    path_cost(&grid, &one_way),
+                    "{algorithm:?}"
+                );
+            }
+        }
+    }
+
+    // Hops to the nearest end counted the plain way, nothing kept but the distances
+    fn fewest_hops(grid: &Grid, start: (usize, usize), wrap: bool) -> Option<usize> {
+        let mut hops = vec![vec![usize::MAX; grid.cols()]; grid.rows()];
+        hops[start.0][start.1] = 0;
+        let mut queue = VecDeque::from([start]);
+        while let Some(pos) = queue.pop_front() {
+            if grid.is_end(pos) {
+                return Some(hops[pos.0][pos.1]);
+            }
+            for dr in -1..=1 {
+                for dc in -1..=1 {
+                    let Some(next) = grid.step(pos, (dr, dc), wrap) else {
+                        continue;
+                    };
+                    if !grid.is_wall(next) && hops[next.0][next.1] == usize::MAX {
+                        hops[next.0][next.1] = hops[pos.0][pos.1] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn bfs_takes_the_fewest_hops_both_ways() {
+        for seed in 0..300 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut grid = Grid::new(rng.gen_range(3..9), rng.gen_range(3..12));
+            for pos in grid.positions().collect::<Vec<_>>() {
+                if rng.gen_bool(0.3) {
+                    grid.set_tile_type(pos, TileType::Wall);
+                }
+            }
+            for _ in 0..rng.gen_range(1..3) {
+                let end = (rng.gen_range(0..grid.rows()), rng.gen_range(0..grid.cols()));
+                grid.set_tile_type(end, TileType::End);
+            }
+            let start = (rng.gen_range(0..grid.rows()), rng.gen_range(0..grid.cols()));
+            if grid.is_wall(start) {
+                continue;
+            }
+            for world_wrap_enabled in [false, true] {
+                let steps = fewest_hops(&grid, start, world_wrap_enabled).map(|hops| hops + 1);
+                for bidirectional in [false, true] {
+                    let options = Options {
+                        world_wrap_enabled,
+                        bidirectional,
+                        ..options(Algorithm::BFS)
+                    };
+                    let path = pathfind(&grid, start, &options).path;
+                    assert_eq!(
+                        Some(path.len()).filter(|&len| len > 0),
+                        steps,
+                        "seed {seed}, wrap {world_wrap_enabled}, bidirectional {bidirectional}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wrap_goes_off_the_edge() {
+        let grid = grid(
+            "\
+.@...
+E@...
+.@...",
+        );
+        let bounded = pathfind(&grid, (1, 4), &options(Algorithm::Dijkstra));
+        assert_eq!(bounded.path.len(), 0);
+        let wrapped = Options {
+            world_wrap_enabled: true,
+            ..options(Algorithm::Dijkstra)
+        };
+        assert_eq!(pathfind(&grid, (1, 4), &wrapped).path, vec![(1, 4), (1, 0)]);
+    }
+
+    #[test]
+    fn start_tile_wins_when_asked() {
+        let grid = grid("B...E");
+        let from_start_tile = Options {
+            use_start_tile: true,
+            ..options(Algorithm::Dijkstra)
+        };
+        assert_eq!(pathfind(&grid, (0, 3), &from_start_tile).path[0], (0, 0));
+        assert_eq!(
+            pathfind(&grid, (0, 3), &options(Algorithm::Dijkstra)).path[0],
+            (0, 3)
+        );
+    }
+
+    #[test]
+    fn path_cost_adds_up_straight_and_diagonal_steps() {
+        let grid = grid(
+            "\
+.R.
+..S
+..W",
+        );
+        assert_eq!(path_cost(&grid, &[(0, 0)]), 0);
+        // Road straight, mud diagonal, water straight
+        assert_eq!(
+            path_cost(&grid, &[(0, 0), (0, 1), (1, 2), (2, 2)]),
+            5 + 42 + 50
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use super::{
+    node::{nodes_from_grid, Node},
+    util::{directions_for, path_from_previous, step_cost},
+    Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::Grid;
+use std::collections::BinaryHeap;
+
+pub fn astar(
+    tiles: &Grid,
+    current_tile_pos: (usize, usize),
+    is_aggressive: bool,
+    options: &Options,
+) -> SearchResult {
+    let mut grid = nodes_from_grid(tiles);
+    let ends = tiles.ends();
+    let min_cost = tiles.min_cost();
+    let mut heap = BinaryHeap::new();
+    let mut visited_order = vec![];
+    let mut reached_end = None;
+    let h_score = nearest_hscore(
+        tiles,
+        current_tile_pos,
+        &ends,
+        options.world_wrap_enabled,
+        is_aggressive,
+        min_cost,
+    );
+    grid[current_tile_pos.0][current_tile_pos.1].g_score = 0;
+    heap.push(Node {
+        distance: h_score,
+        g_score: 0,
+        ..grid[current_tile_pos.0][current_tile_pos.1]
+    });
+
+    let directions = directions_for(options);
+
+    while let Some(node) = heap.pop() {
+        let current_node = &mut grid[node.row][node.col];
+        if tiles.is_end((node.row, node.col)) {
+            reached_end = Some((node.row, node.col));
+            break;
+        }
+
+        if current_node.visited || node.g_score > current_node.g_score {
+            continue;
+        }
+
+        current_node.visited = true;
+
+        let mut opened = vec![];
+        for (dr, dc) in directions {
+            let Some((visit_row, visit_col)) =
+                tiles.step((node.row, node.col), (dr, dc), options.world_wrap_enabled)
+            else {
+                continue;
+            };
+
+            let checked_node = &mut grid[visit_row][visit_col];
+
+            if checked_node.is_wall {
+                continue;
+            }
+
+            let directional_distance = step_cost(checked_node.cost, (dr, dc));
+
+            let potential_g = node.g_score + directional_distance;
+
+            let h_score = nearest_hscore(
+                tiles,
+                (visit_row, visit_col),
+                &ends,
+                options.world_wrap_enabled,
+                is_aggressive,
+                min_cost,
+            );
+
+            if potential_g < checked_node.g_score {
+                checked_node.distance = potential_g + h_score;
+                checked_node.g_score = potential_g;
+                checked_node.previous_node = Some((node.row, node.col));
+                checked_node.visited = false;
+                heap.push(Node { ..*checked_node });
+                opened.push((visit_row, visit_col));
+            }
+        }
+        visited_order.push(SearchNode {
+            pos: (node.row, node.col),
+            side: SearchSide::Start,
+            g_score: node.g_score,
+            previous_node: grid[node.row][node.col].previous_node,
+            opened,
+        });
+    }
+
+    SearchResult {
+        visited: visited_order,
+        path: reached_end
+            .map(|end_pos| path_from_previous(&grid, current_tile_pos, end_pos))
+            .unwrap_or_default(),
+    }
+}
+
+// Estimate to whichever end is closest, still never overshoots the real nearest one
+pub(super) fn nearest_hscore(
+    tiles: &Grid,
+    pos: (usize, usize),
+    ends: &[(usize, usize)],
+    world_wrap_enabled: bool,
+    is_aggressive: bool,
+    min_cost: usize,
+) -> usize {
+    ends.iter()
+        .map(|&end_pos| {
+            hscore(
+                tiles,
+                pos,
+                end_pos,
+                world_wrap_enabled,
+                is_aggressive,
+                min_cost,
+            )
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+pub(super) fn hscore(
+    tiles: &Grid,
+    (current_row, current_col): (usize, usize),
+    (end_row, end_col): (usize, usize),
+    world_wrap_enabled: bool,
+    is_aggressive: bool,
+    // Cheapest tile on the grid, see `Grid::min_cost`
+    min_cost: usize,
+) -> usize {
+    let rows = tiles.rows() as isize;
+    let cols = tiles.cols() as isize;
+    let mut dx = end_col as isize - current_col as isize;
+    let mut dy = end_row as isize - current_row as isize;
+    if dx.abs() > cols / 2 && world_wrap_enabled {
+        dx = cols - dx.abs();
+    }
+    if dy.abs() > rows / 2 && world_wrap_enabled {
+        dy = rows - dy.abs();
+    }
+    let distance_between_checked_and_end = ((dx.pow(2) + dy.pow(2)) as f64).sqrt();
+
+    if is_aggressive {
+        (distance_between_checked_and_end as usize).pow(10)
+    } else {
+        // Cheapest tile on this grid so the estimate never overshoots across a road, and stays as
+        // tight as it can on grids without any
+        distance_between_checked_and_end as usize * min_cost
+    }
+}
@@ -0,0 +1,72 @@
+use super::{
+    node::nodes_from_grid,
+    util::{directions_for, path_from_previous},
+    Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::Grid;
+use std::collections::VecDeque;
+
+pub fn bfs(tiles: &Grid, current_tile_pos: (usize, usize), options: &Options) -> SearchResult {
+    let mut grid = nodes_from_grid(tiles);
+    let mut reached_end = None;
+    let mut visited = vec![];
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::default();
+
+    // Tiles count as visited once they're queued, whoever finds one first found it in the fewest
+    // hops and stays its parent
+    queue.push_front(current_tile_pos);
+    grid[current_tile_pos.0][current_tile_pos.1].g_score = 0;
+    grid[current_tile_pos.0][current_tile_pos.1].visited = true;
+
+    let directions = directions_for(options);
+
+    while let Some((row, col)) = queue.pop_back() {
+        let hops = grid[row][col].g_score;
+        let previous_node = grid[row][col].previous_node;
+
+        let mut opened = vec![];
+        if tiles.is_end((row, col)) {
+            visited.push(SearchNode {
+                pos: (row, col),
+                side: SearchSide::Start,
+                g_score: hops,
+                previous_node,
+                opened,
+            });
+            reached_end = Some((row, col));
+            break;
+        }
+        // let mut rng = thread_rng();
+        // directions.shuffle(&mut rng);
+
+        for (dr, dc) in directions {
+            let Some((visit_row, visit_col)) =
+                tiles.step((row, col), (dr, dc), options.world_wrap_enabled)
+            else {
+                continue;
+            };
+
+            let checked_node = &mut grid[visit_row][visit_col];
+            if !checked_node.visited {
+                checked_node.visited = true;
+                checked_node.previous_node = Some((row, col));
+                checked_node.g_score = hops + 1;
+                queue.push_front((visit_row, visit_col));
+                opened.push((visit_row, visit_col));
+            }
+        }
+        visited.push(SearchNode {
+            pos: (row, col),
+            side: SearchSide::Start,
+            g_score: hops,
+            previous_node,
+            opened,
+        });
+    }
+
+    let path = reached_end
+        .map(|end_pos| path_from_previous(&grid, current_tile_pos, end_pos))
+        .unwrap_or_default();
+
+    SearchResult { visited, path }
+}
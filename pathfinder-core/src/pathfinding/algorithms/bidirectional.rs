@@ -0,0 +1,328 @@
+use super::{
+    astar::nearest_hscore,
+    node::{nodes_from_grid, Node},
+    util::{directions_for, step_cost},
+    Algorithm, Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::Grid;
+use std::collections::{BinaryHeap, VecDeque};
+
+/*
+ * Run the same search from the current tile and from the end tiles (all at once), taking turns.
+ * Each side gets its own copy of the grid so it can keep its own distances and previous nodes.
+ * Once a node has been reached by both sides we know of a full path through it,
+ * and we keep going only as long as the frontiers could still find something shorter.
+ */
+
+pub fn bidirectional(
+    tiles: &Grid,
+    current_tile_pos: (usize, usize),
+    options: &Options,
+) -> SearchResult {
+    let nodes = nodes_from_grid(tiles);
+    let ends = tiles.ends();
+
+    match options.algorithm {
+        Algorithm::BFS => bidirectional_bfs(tiles, nodes, current_tile_pos, ends, options),
+        Algorithm::AStar => {
+            bidirectional_best_first(tiles, nodes, current_tile_pos, ends, Some(false), options)
+        }
+        Algorithm::AggressiveStar => {
+            bidirectional_best_first(tiles, nodes, current_tile_pos, ends, Some(true), options)
+        }
+        _ => bidirectional_best_first(tiles, nodes, current_tile_pos, ends, None, options),
+    }
+}
+
+struct Frontier {
+    grid: Vec<Vec<Node>>,
+    heap: BinaryHeap<Node>,
+    queue: VecDeque<(usize, usize)>,
+    // Where this side starts from, every end at once for the End side
+    origins: Vec<(usize, usize)>,
+    targets: Vec<(usize, usize)>,
+    side: SearchSide,
+}
+
+impl Frontier {
+    fn new(
+        mut grid: Vec<Vec<Node>>,
+        origins: Vec<(usize, usize)>,
+        targets: Vec<(usize, usize)>,
+        side: SearchSide,
+    ) -> Frontier {
+        for &(row, col) in &origins {
+            grid[row][col].g_score = 0;
+        }
+        Frontier {
+            grid,
+            heap: BinaryHeap::new(),
+            queue: VecDeque::default(),
+            origins,
+            targets,
+            side,
+        }
+    }
+
+    fn reached(&self, (row, col): (usize, usize)) -> bool {
+        self.grid[row][col].g_score != usize::MAX
+    }
+
+    // Previous nodes from `pos` back to wherever this side started, `pos` included
+    fn chain_from(&self, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut chain = vec![pos];
+        let mut head = pos;
+        while let Some(previous) = self.grid[head.0][head.1].previous_node {
+            chain.push(previous);
+            head = previous;
+        }
+        chain
+    }
+}
+
+// Dijkstra when `is_aggressive` is None, A* otherwise
+fn bidirectional_best_first(
+    tiles: &Grid,
+    grid: Vec<Vec<Node>>,
+    current_tile_pos: (usize, usize),
+    ends: Vec<(usize, usize)>,
+    is_aggressive: Option<bool>,
+    options: &Options,
+) -> SearchResult {
+    let starts_on_end = ends.contains(&current_tile_pos);
+    let mut sides = [
+        Frontier::new(
+            grid.clone(),
+            vec![current_tile_pos],
+            ends.clone(),
+            SearchSide::Start,
+        ),
+        Frontier::new(grid, ends, vec![current_tile_pos], SearchSide::End),
+    ];
+    let min_cost = tiles.min_cost();
+    let priority = |pos: (usize, usize), targets: &[(usize, usize)], g_score: usize| {
+        g_score
+            + is_aggressive
+                .map(|aggressive| {
+                    nearest_hscore(
+                        tiles,
+                        pos,
+                        targets,
+                        options.world_wrap_enabled,
+                        aggressive,
+                        min_cost,
+                    )
+                })
+                .unwrap_or(0)
+    };
+    for frontier in &mut sides {
+        for &(row, col) in &frontier.origins {
+            frontier.grid[row][col].distance = priority((row, col), &frontier.targets, 0);
+            frontier.heap.push(Node {
+                ..frontier.grid[row][col]
+            });
+        }
+    }
+
+    let directions = directions_for(options);
+    let mut visited_order = vec![];
+    let mut best_cost = usize::MAX;
+    let mut meeting: Option<(usize, usize)> = None;
+    let mut turn = 0;
+
+    if starts_on_end {
+        best_cost = 0;
+        meeting = Some(current_tile_pos);
+    }
+
+    while let (Some(top_start), Some(top_end)) = (sides[0].heap.peek(), sides[1].heap.peek()) {
+        // Dijkstra can stop once the two closest frontier nodes can't beat the best meeting,
+        // A* once either side's best estimate can't.
+        let lower_bound = if is_aggressive.is_some() {
+            top_start.distance.max(top_end.distance)
+        } else {
+            top_start.distance.saturating_add(top_end.distance)
+        };
+        if meeting.is_some() && lower_bound >= best_cost {
+            break;
+        }
+
+        let (current, other) = if turn == 0 {
+            let (a, b) = sides.split_at_mut(1);
+            (&mut a[0], &b[0])
+        } else {
+            let (a, b) = sides.split_at_mut(1);
+            (&mut b[0], &a[0])
+        };
+        turn = 1 - turn;
+
+        let Some(node) = current.heap.pop() else {
+            continue;
+        };
+        let current_node = &mut current.grid[node.row][node.col];
+        if current_node.visited || node.g_score > current_node.g_score {
+            continue;
+        }
+        current_node.visited = true;
+
+        let mut opened = vec![];
+        for (dr, dc) in directions {
+            let Some((visit_row, visit_col)) =
+                tiles.step((node.row, node.col), (dr, dc), options.world_wrap_enabled)
+            else {
+                continue;
+            };
+
+            if current.grid[visit_row][visit_col].is_wall {
+                continue;
+            }
+
+            // Walking back from the end, the step we're undoing was onto the node we're expanding
+            let stepped_onto = match current.side {
+                SearchSide::Start => current.grid[visit_row][visit_col].cost,
+                SearchSide::End => node.cost,
+            };
+            let potential_g = node.g_score + step_cost(stepped_onto, (dr, dc));
+
+            if potential_g < current.grid[visit_row][visit_col].g_score {
+                let distance = priority((visit_row, visit_col), &current.targets, potential_g);
+                let checked_node = &mut current.grid[visit_row][visit_col];
+                checked_node.g_score = potential_g;
+                checked_node.distance = distance;
+                checked_node.previous_node = Some((node.row, node.col));
+                checked_node.visited = false;
+                current.heap.push(Node { ..*checked_node });
+                opened.push((visit_row, visit_col));
+            }
+
+            if other.reached((visit_row, visit_col)) {
+                let total = current.grid[visit_row][visit_col].g_score
+                    + other.grid[visit_row][visit_col].g_score;
+                if total < best_cost {
+                    best_cost = total;
+                    meeting = Some((visit_row, visit_col));
+                }
+            }
+        }
+        visited_order.push(SearchNode {
+            pos: (node.row, node.col),
+            side: current.side,
+            g_score: node.g_score,
+            previous_node: current.grid[node.row][node.col].previous_node,
+            opened,
+        });
+    }
+
+    let path = meeting
+        .map(|pos| join_path(&sides[0], &sides[1], pos))
+        .unwrap_or_default();
+    SearchResult {
+        visited: visited_order,
+        path,
+    }
+}
+
+fn bidirectional_bfs(
+    tiles: &Grid,
+    grid: Vec<Vec<Node>>,
+    current_tile_pos: (usize, usize),
+    ends: Vec<(usize, usize)>,
+    options: &Options,
+) -> SearchResult {
+    let starts_on_end = ends.contains(&current_tile_pos);
+    let mut sides = [
+        Frontier::new(
+            grid.clone(),
+            vec![current_tile_pos],
+            ends.clone(),
+            SearchSide::Start,
+        ),
+        Frontier::new(grid, ends, vec![current_tile_pos], SearchSide::End),
+    ];
+
+    let directions = directions_for(options);
+    let mut visited_order = vec![];
+    let mut meeting: Option<(usize, usize)> = None;
+    let mut turn = 0;
+
+    for frontier in &mut sides {
+        frontier.queue.extend(frontier.origins.iter().copied());
+    }
+    if starts_on_end {
+        meeting = Some(current_tile_pos);
+    }
+
+    // A whole layer per turn. Before a layer no tile had been reached by both sides, so every path
+    // is longer than both sides' depths put together and the best meeting in the first layer that
+    // finds any is as short as it gets.
+    while meeting.is_none() && !sides[0].queue.is_empty() && !sides[1].queue.is_empty() {
+        let (current, other) = if turn == 0 {
+            let (a, b) = sides.split_at_mut(1);
+            (&mut a[0], &b[0])
+        } else {
+            let (a, b) = sides.split_at_mut(1);
+            (&mut b[0], &a[0])
+        };
+        turn = 1 - turn;
+
+        let mut best_hops = usize::MAX;
+        let layer: Vec<(usize, usize)> = current.queue.drain(..).collect();
+        for (row, col) in layer {
+            let mut opened = vec![];
+            for (dr, dc) in directions {
+                let Some((visit_row, visit_col)) =
+                    tiles.step((row, col), (dr, dc), options.world_wrap_enabled)
+                else {
+                    continue;
+                };
+
+                let checked_node = &current.grid[visit_row][visit_col];
+                if checked_node.is_wall || current.reached((visit_row, visit_col)) {
+                    continue;
+                }
+
+                let hops = current.grid[row][col].g_score + 1;
+                let checked_node = &mut current.grid[visit_row][visit_col];
+                checked_node.g_score = hops;
+                checked_node.previous_node = Some((row, col));
+                current.queue.push_back((visit_row, visit_col));
+                opened.push((visit_row, visit_col));
+
+                if other.reached((visit_row, visit_col)) {
+                    let total = hops + other.grid[visit_row][visit_col].g_score;
+                    if total < best_hops {
+                        best_hops = total;
+                        meeting = Some((visit_row, visit_col));
+                    }
+                }
+            }
+            visited_order.push(SearchNode {
+                pos: (row, col),
+                side: current.side,
+                g_score: current.grid[row][col].g_score,
+                previous_node: current.grid[row][col].previous_node,
+                opened,
+            });
+        }
+    }
+
+    let path = meeting
+        .map(|pos| join_path(&sides[0], &sides[1], pos))
+        .unwrap_or_default();
+    SearchResult {
+        visited: visited_order,
+        path,
+    }
+}
+
+// Start side's previous nodes up to the meeting, then the end side's back out to the end
+fn join_path(
+    start_side: &Frontier,
+    end_side: &Frontier,
+    meeting: (usize, usize),
+) -> Vec<(usize, usize)> {
+    let mut path = start_side.chain_from(meeting);
+    path.reverse();
+    path.extend(end_side.chain_from(meeting).into_iter().skip(1));
+    path
+}
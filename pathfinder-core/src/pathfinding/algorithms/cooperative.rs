@@ -0,0 +1,318 @@
+use super::{pathfind, util::step_cost, Options};
+use crate::grid::Grid;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+// Waiting is on top of how long the path would take alone, enough for everyone ahead to go by
+const WAIT_SLACK_PER_AGENT: usize = 2;
+const MIN_WAIT_SLACK: usize = 8;
+
+// Two agents on the same tile at the same step, or swapping tiles during it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub time: usize,
+    pub pos: (usize, usize),
+    pub agents: (usize, usize),
+}
+
+/*
+ * One path per agent, where it is at every step (waits repeat a tile). Every agent moves one tile
+ * per step, all together.
+ *
+ * Not cooperative, each one is just the chosen algorithm's path as if nobody else was around.
+ * Cooperative, agents plan one after another with A* through space and time, and a reservation
+ * table keeps them off every tile (and swap) the agents before them already claimed. An agent
+ * that can't get around them in time falls back to its solo path, so that one can still conflict.
+ *
+ * Ends aren't reserved, agents that get there are done and out of the way. Agents that can't get
+ * to one stay put, and everyone goes around them.
+ */
+pub fn agent_paths(
+    grid: &Grid,
+    starts: &[(usize, usize)],
+    options: &Options,
+    cooperative: bool,
+) -> Vec<Vec<(usize, usize)>> {
+    let solo_options = Options {
+        use_start_tile: false,
+        visit_all_goals: false,
+        ..options.clone()
+    };
+    let solo_paths: Vec<Vec<(usize, usize)>> = starts
+        .iter()
+        .map(|&start| {
+            let path = pathfind(grid, start, &solo_options).path;
+            if path.is_empty() {
+                vec![start]
+            } else {
+                path
+            }
+        })
+        .collect();
+    if !cooperative {
+        return solo_paths;
+    }
+
+    let mut reservations = Reservations::default();
+    // Stuck agents first, they're in the way for good
+    for path in solo_paths
+        .iter()
+        .filter(|path| !grid.is_end(path[path.len() - 1]))
+    {
+        reservations.reserve(grid, path);
+    }
+    let wait_slack = MIN_WAIT_SLACK.max(WAIT_SLACK_PER_AGENT * starts.len());
+    solo_paths
+        .into_iter()
+        .map(|solo_path| {
+            if !grid.is_end(solo_path[solo_path.len() - 1]) {
+                return solo_path;
+            }
+            let horizon = solo_path.len() - 1 + wait_slack;
+            let path = space_time_astar(grid, solo_path[0], &reservations, horizon, options)
+                .unwrap_or(solo_path);
+            reservations.reserve(grid, &path);
+            path
+        })
+        .collect()
+}
+
+// Every step where two agents run into each other, ends don't count
+pub fn find_conflicts(grid: &Grid, paths: &[Vec<(usize, usize)>]) -> Vec<Conflict> {
+    let at = |path: &[(usize, usize)], time: usize| path[time.min(path.len() - 1)];
+    let steps = paths.iter().map(|path| path.len()).max().unwrap_or(0);
+    let mut conflicts = vec![];
+    for time in 0..steps {
+        for a in 0..paths.len() {
+            for b in a + 1..paths.len() {
+                let (pos_a, pos_b) = (at(&paths[a], time), at(&paths[b], time));
+                let shared = pos_a == pos_b && !grid.is_end(pos_a);
+                let swapped = time > 0
+                    && pos_a != pos_b
+                    && pos_a == at(&paths[b], time - 1)
+                    && pos_b == at(&paths[a], time - 1);
+                if shared || swapped {
+                    conflicts.push(Conflict {
+                        time,
+                        pos: pos_a,
+                        agents: (a, b),
+                    });
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+// (from, to, step it left from)
+type Move = ((usize, usize), (usize, usize), usize);
+
+#[derive(Default)]
+struct Reservations {
+    // (tile, step)
+    tiles: HashSet<((usize, usize), usize)>,
+    moves: HashSet<Move>,
+    // Taken from this step on
+    parked: HashMap<(usize, usize), usize>,
+}
+
+impl Reservations {
+    fn reserve(&mut self, grid: &Grid, path: &[(usize, usize)]) {
+        for (time, &pos) in path.iter().enumerate() {
+            if !grid.is_end(pos) {
+                self.tiles.insert((pos, time));
+            }
+            if let Some(&next) = path.get(time + 1) {
+                self.moves.insert((pos, next, time));
+            }
+        }
+        let last = path[path.len() - 1];
+        if !grid.is_end(last) {
+            self.parked.insert(last, path.len() - 1);
+        }
+    }
+
+    fn is_free(&self, grid: &Grid, from: (usize, usize), to: (usize, usize), time: usize) -> bool {
+        if grid.is_end(to) {
+            return true;
+        }
+        !self.tiles.contains(&(to, time + 1))
+            && !self.moves.contains(&(to, from, time))
+            && self.parked.get(&to).is_none_or(|&since| time + 1 < since)
+    }
+}
+
+// Chebyshev steps to the nearest end on the cheapest tile, never more than it really costs
+fn heuristic(
+    grid: &Grid,
+    ends: &[(usize, usize)],
+    pos: (usize, usize),
+    wrap: bool,
+    min_cost: usize,
+) -> usize {
+    ends.iter()
+        .map(|end| {
+            let mut rows = pos.0.abs_diff(end.0);
+            let mut cols = pos.1.abs_diff(end.1);
+            if wrap {
+                rows = rows.min(grid.rows() - rows);
+                cols = cols.min(grid.cols() - cols);
+            }
+            rows.max(cols) * min_cost
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+// A* where a node is a tile at a step, waiting in place is a move too
+fn space_time_astar(
+    grid: &Grid,
+    start: (usize, usize),
+    reservations: &Reservations,
+    horizon: usize,
+    options: &Options,
+) -> Option<Vec<(usize, usize)>> {
+    let wrap = options.world_wrap_enabled;
+    let ends = grid.ends();
+    let min_cost = grid.min_cost();
+    let mut moves = vec![(0, 0)];
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            if (dr, dc) != (0, 0) {
+                moves.push((dr, dc));
+            }
+        }
+    }
+
+    let mut heap = BinaryHeap::new();
+    let mut g_scores: HashMap<((usize, usize), usize), usize> = HashMap::new();
+    let mut previous: HashMap<((usize, usize), usize), (usize, usize)> = HashMap::new();
+    g_scores.insert((start, 0), 0);
+    heap.push(Reverse((
+        heuristic(grid, &ends, start, wrap, min_cost),
+        0,
+        start,
+    )));
+
+    while let Some(Reverse((_, time, pos))) = heap.pop() {
+        if grid.is_end(pos) {
+            let mut path = vec![pos];
+            let mut node = (pos, time);
+            while let Some(&prev) = previous.get(&node) {
+                path.push(prev);
+                node = (prev, node.1 - 1);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if time >= horizon {
+            continue;
+        }
+        let g_score = g_scores[&(pos, time)];
+        for &delta in &moves {
+            let next = if delta == (0, 0) {
+                pos
+            } else {
+                match grid.step(pos, delta, wrap) {
+                    Some(next) => next,
+                    None => continue,
+                }
+            };
+            if grid.is_wall(next) || !reservations.is_free(grid, pos, next, time) {
+                continue;
+            }
+            // Standing still costs the same as stepping back onto the tile
+            let cost = if delta == (0, 0) {
+                grid.cost(pos)
+            } else {
+                step_cost(grid.cost(next), delta)
+            };
+            let next_g_score = g_score + cost;
+            let node = (next, time + 1);
+            if g_scores
+                .get(&node)
+                .is_some_and(|&known| known <= next_g_score)
+            {
+                continue;
+            }
+            g_scores.insert(node, next_g_score);
+            previous.insert(node, pos);
+            let f_score = next_g_score + heuristic(grid, &ends, next, wrap, min_cost);
+            heap.push(Reverse((f_score, time + 1, next)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::parse_map;
+    use crate::pathfinding::algorithms::Algorithm;
+
+    fn options() -> Options {
+        Options {
+            algorithm: Algorithm::AStar,
+            world_wrap_enabled: false,
+            ..Options::default()
+        }
+    }
+
+    // Everyone squeezes through the one gap in the wall
+    fn bottleneck() -> Grid {
+        parse_map(
+            "\
+.....@....
+.....@....
+..........
+.....@...E
+.....@....",
+        )
+        .unwrap()
+        .grid
+    }
+
+    // One tile or a wait per step, and ends on an end
+    fn assert_walkable(grid: &Grid, path: &[(usize, usize)]) {
+        assert!(grid.is_end(path[path.len() - 1]));
+        for step in path.windows(2) {
+            assert!(step[0].0.abs_diff(step[1].0) <= 1 && step[0].1.abs_diff(step[1].1) <= 1);
+            assert!(!grid.is_wall(step[1]));
+        }
+    }
+
+    #[test]
+    fn solo_paths_run_into_each_other() {
+        let grid = bottleneck();
+        let paths = agent_paths(&grid, &[(0, 0), (2, 0), (4, 0)], &options(), false);
+        assert!(!find_conflicts(&grid, &paths).is_empty());
+    }
+
+    #[test]
+    fn cooperative_paths_take_turns() {
+        let grid = bottleneck();
+        let starts = [(0, 0), (2, 0), (4, 0), (1, 1), (3, 1)];
+        let paths = agent_paths(&grid, &starts, &options(), true);
+        assert_eq!(find_conflicts(&grid, &paths), vec![]);
+        for (path, start) in paths.iter().zip(starts) {
+            assert_eq!(path[0], start);
+            assert_walkable(&grid, path);
+        }
+    }
+
+    #[test]
+    fn swapping_tiles_is_a_conflict() {
+        let grid = Grid::new(1, 2);
+        let paths = [vec![(0, 0), (0, 1)], vec![(0, 1), (0, 0)]];
+        assert_eq!(
+            find_conflicts(&grid, &paths),
+            vec![Conflict {
+                time: 1,
+                pos: (0, 1),
+                agents: (0, 1),
+            }]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use super::{
+    node::{nodes_from_grid, Node},
+    Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::Grid;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/*
+ * Okay here's the plan for dfs
+ * From current node, add children in directions
+ * Then we have tree
+ * Pass tree to dfs
+ * Search with DFS
+ * Gonna not do a tree because the children are known
+ */
+
+/*
+ * DFS would involve following the first unvisited child
+ * BFS would involve searching all of a nodes children, then moving on to process their children
+ */
+
+pub fn dfs(tiles: &Grid, current_tile_pos: (usize, usize), options: &Options) -> SearchResult {
+    let mut nodes = nodes_from_grid(tiles);
+    let mut visited = vec![];
+    let mut path = vec![];
+
+    search(
+        tiles,
+        &mut nodes,
+        current_tile_pos,
+        0,
+        &mut visited,
+        &mut path,
+        options,
+    );
+
+    // Built up while unwinding, so it's end first
+    path.reverse();
+    SearchResult { visited, path }
+}
+
+fn search(
+    tiles: &Grid,
+    grid: &mut [Vec<Node>],
+    current_tile_pos: (usize, usize),
+    depth: usize,
+    visited: &mut Vec<SearchNode>,
+    path: &mut Vec<(usize, usize)>,
+    options: &Options,
+) -> bool {
+    let current_tile_node = &mut grid[current_tile_pos.0][current_tile_pos.1];
+
+    let is_end_tile = tiles.is_end(current_tile_pos);
+
+    if current_tile_node.visited {
+        return false;
+    }
+    current_tile_node.visited = true;
+    let previous_node = current_tile_node.previous_node;
+
+    if is_end_tile {
+        visited.push(SearchNode {
+            pos: current_tile_pos,
+            side: SearchSide::Start,
+            g_score: depth,
+            previous_node,
+            opened: vec![],
+        });
+        path.push(current_tile_pos);
+        return true;
+    }
+
+    let mut directions = [
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+        (1, 1),
+        (0, 1),
+        (1, 0),
+        (0, -1),
+        (-1, 0),
+    ];
+    let mut in_path = false;
+
+    directions.rotate_left(options.direction_offset);
+    if options.random_direction {
+        // Seeded per tile so a tile shuffles the same way every run
+        let tile_index = current_tile_pos.0 * tiles.cols() + current_tile_pos.1;
+        let mut rng = StdRng::seed_from_u64(options.seed.wrapping_add(tile_index as u64));
+        directions.shuffle(&mut rng);
+    }
+
+    // The frontier is the recursion stack, every neighbor not visited yet waits there its turn
+    let opened = directions
+        .iter()
+        .filter_map(|&delta| tiles.step(current_tile_pos, delta, options.world_wrap_enabled))
+        .filter(|&(row, col)| !grid[row][col].visited)
+        .collect();
+    visited.push(SearchNode {
+        pos: current_tile_pos,
+        side: SearchSide::Start,
+        g_score: depth,
+        previous_node,
+        opened,
+    });
+
+    for (dr, dc) in directions {
+        let Some(visit_pos) = tiles.step(current_tile_pos, (dr, dc), options.world_wrap_enabled)
+        else {
+            continue;
+        };
+        // Only the call that actually enters a tile gets to be its parent
+        if !grid[visit_pos.0][visit_pos.1].visited {
+            grid[visit_pos.0][visit_pos.1].previous_node = Some(current_tile_pos);
+        }
+        in_path |= search(tiles, grid, visit_pos, depth + 1, visited, path, options);
+        if in_path {
+            path.push(current_tile_pos);
+            break;
+        }
+    }
+
+    in_path
+}
@@ -0,0 +1,73 @@
+use super::{
+    node::{nodes_from_grid, Node},
+    util::{directions_for, path_from_previous, step_cost},
+    Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::Grid;
+use std::collections::BinaryHeap;
+
+pub fn dijkstra(tiles: &Grid, current_tile_pos: (usize, usize), options: &Options) -> SearchResult {
+    let mut grid = nodes_from_grid(tiles);
+    let mut heap = BinaryHeap::new();
+    let mut visited_order = vec![];
+    grid[current_tile_pos.0][current_tile_pos.1].distance = 0;
+    heap.push(Node {
+        distance: 0,
+        ..grid[current_tile_pos.0][current_tile_pos.1]
+    });
+
+    let directions = directions_for(options);
+
+    let mut reached_end = None;
+    while let Some(node) = heap.pop() {
+        if grid[node.row][node.col].visited || node.distance > grid[node.row][node.col].distance {
+            continue;
+        }
+
+        if tiles.is_end((node.row, node.col)) {
+            reached_end = Some((node.row, node.col));
+            break;
+        }
+
+        grid[node.row][node.col].visited = true;
+
+        let mut opened = vec![];
+        for (dr, dc) in directions {
+            let Some((visit_row, visit_col)) =
+                tiles.step((node.row, node.col), (dr, dc), options.world_wrap_enabled)
+            else {
+                continue;
+            };
+
+            if grid[visit_row][visit_col].is_wall {
+                continue;
+            }
+
+            let checked_node = &mut grid[visit_row][visit_col];
+            let directional_distance = step_cost(checked_node.cost, (dr, dc));
+            let new_distance = node.distance + directional_distance;
+
+            if new_distance < checked_node.distance {
+                checked_node.distance = new_distance;
+                checked_node.previous_node = Some((node.row, node.col));
+                checked_node.visited = false;
+                heap.push(Node { ..*checked_node });
+                opened.push((visit_row, visit_col));
+            }
+        }
+        visited_order.push(SearchNode {
+            pos: (node.row, node.col),
+            side: SearchSide::Start,
+            g_score: node.distance,
+            previous_node: grid[node.row][node.col].previous_node,
+            opened,
+        });
+    }
+
+    SearchResult {
+        visited: visited_order,
+        path: reached_end
+            .map(|end_pos| path_from_previous(&grid, current_tile_pos, end_pos))
+            .unwrap_or_default(),
+    }
+}
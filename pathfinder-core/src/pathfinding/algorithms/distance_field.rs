@@ -0,0 +1,42 @@
+use super::util::step_cost;
+use crate::grid::Grid;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+// What it costs to walk from the nearest of `sources` to every tile, usize::MAX where you can't
+pub fn distance_field(
+    grid: &Grid,
+    sources: &[(usize, usize)],
+    world_wrap_enabled: bool,
+) -> Vec<Vec<usize>> {
+    let mut distances = vec![vec![usize::MAX; grid.cols()]; grid.rows()];
+    let mut heap = BinaryHeap::new();
+    for &(row, col) in sources {
+        distances[row][col] = 0;
+        heap.push(Reverse((0, (row, col))));
+    }
+
+    while let Some(Reverse((distance, pos))) = heap.pop() {
+        if distance > distances[pos.0][pos.1] {
+            continue;
+        }
+        for dr in -1..=1 {
+            for dc in -1..=1 {
+                if (dr, dc) == (0, 0) {
+                    continue;
+                }
+                let Some(next) = grid.step(pos, (dr, dc), world_wrap_enabled) else {
+                    continue;
+                };
+                if grid.is_wall(next) {
+                    continue;
+                }
+                let next_distance = distance + step_cost(grid.cost(next), (dr, dc));
+                if next_distance < distances[next.0][next.1] {
+                    distances[next.0][next.1] = next_distance;
+                    heap.push(Reverse((next_distance, next)));
+                }
+            }
+        }
+    }
+    distances
+}
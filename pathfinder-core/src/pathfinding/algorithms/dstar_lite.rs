@@ -0,0 +1,312 @@
+use super::{
+    util::{directions_for, step_cost},
+    Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::Grid;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/*
+ * D* Lite searches backward, from every end at once toward the start, and hangs on to everything
+ * it learned. When tiles change or the start moves, only the part of the search the change
+ * actually touches gets expanded again instead of starting over.
+ *
+ * `g` is a tile's cost to the nearest end as of its last expansion, `rhs` is what it would be
+ * looking one step ahead at its neighbors. Where they disagree the tile is inconsistent and waits
+ * in the queue to be fixed. Costs are the same ones Dijkstra and A* pay.
+ */
+
+// (estimated total cost, cost to the end), smallest first
+type Key = (usize, usize);
+
+pub struct DStarLite {
+    grid: Grid,
+    options: Options,
+    start: (usize, usize),
+    // Added to every key as the start moves so keys already in the queue stay comparable
+    km: usize,
+    g: Vec<Vec<usize>>,
+    rhs: Vec<Vec<usize>>,
+    // Entries go stale instead of being removed, `top_key` throws those away
+    queue: BinaryHeap<Reverse<(Key, (usize, usize))>>,
+    directions: [(isize, isize); 8],
+    // What the heuristic scales steps by, see `Grid::min_cost`
+    min_cost: usize,
+}
+
+impl DStarLite {
+    pub fn new(grid: &Grid, start: (usize, usize), options: &Options) -> DStarLite {
+        let mut dstar = DStarLite {
+            grid: grid.clone(),
+            options: options.clone(),
+            start,
+            km: 0,
+            g: vec![vec![usize::MAX; grid.cols()]; grid.rows()],
+            rhs: vec![vec![usize::MAX; grid.cols()]; grid.rows()],
+            queue: BinaryHeap::new(),
+            directions: directions_for(options),
+            min_cost: grid.min_cost(),
+        };
+        for (row, col) in grid.ends() {
+            dstar.rhs[row][col] = 0;
+            let key = dstar.key((row, col));
+            dstar.queue.push(Reverse((key, (row, col))));
+        }
+        dstar
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    // Doesn't expand anything on its own, the next search picks it up
+    pub fn move_start(&mut self, start: (usize, usize)) {
+        self.km += self.heuristic(self.start, start);
+        self.start = start;
+    }
+
+    // Every tile that changed since the last grid, and its neighbors, gets looked at again
+    pub fn update_grid(&mut self, grid: &Grid) {
+        // Every key in the queue was worked out with the old heuristic, start over instead
+        if grid.min_cost() != self.min_cost {
+            *self = DStarLite::new(grid, self.start, &self.options);
+            return;
+        }
+        let changed: Vec<(usize, usize)> = grid
+            .positions()
+            .filter(|&pos| grid.tile_type(pos) != self.grid.tile_type(pos))
+            .collect();
+        self.grid = grid.clone();
+        for pos in changed {
+            self.update_vertex(pos);
+            for (neighbor, _) in self.neighbors(pos) {
+                self.update_vertex(neighbor);
+            }
+        }
+    }
+
+    // `visited` is only what had to be expanded since the last search, everything on a fresh one
+    pub fn search(&mut self) -> SearchResult {
+        let mut visited = vec![];
+        while let Some(top_key) = self.top_key() {
+            let (row, col) = self.start;
+            if top_key >= self.key(self.start) && self.g[row][col] == self.rhs[row][col] {
+                break;
+            }
+            let Some(Reverse((old_key, pos))) = self.queue.pop() else {
+                break;
+            };
+            let new_key = self.key(pos);
+            if old_key < new_key {
+                self.queue.push(Reverse((new_key, pos)));
+                continue;
+            }
+
+            let mut opened = vec![];
+            if self.g[pos.0][pos.1] > self.rhs[pos.0][pos.1] {
+                self.g[pos.0][pos.1] = self.rhs[pos.0][pos.1];
+            } else {
+                // Got more expensive, start it over and let the neighbors sort it out
+                self.g[pos.0][pos.1] = usize::MAX;
+                if self.update_vertex(pos) {
+                    opened.push(pos);
+                }
+            }
+            for (neighbor, _) in self.neighbors(pos) {
+                if self.update_vertex(neighbor) {
+                    opened.push(neighbor);
+                }
+            }
+
+            visited.push(SearchNode {
+                pos,
+                side: SearchSide::End,
+                g_score: self.g[pos.0][pos.1].min(self.rhs[pos.0][pos.1]),
+                previous_node: self.best_next(pos).map(|(next, _)| next),
+                opened,
+            });
+        }
+
+        SearchResult {
+            visited,
+            path: self.path(),
+        }
+    }
+
+    // Downhill from the start, always to the neighbor closest to an end
+    fn path(&self) -> Vec<(usize, usize)> {
+        if self.grid.is_wall(self.start) {
+            return vec![];
+        }
+        let mut path = vec![self.start];
+        let mut head = self.start;
+        while !self.grid.is_end(head) {
+            match self.best_next(head) {
+                // Can't be longer than every tile, anything else is going in circles
+                Some((next, _)) if path.len() <= self.grid.rows() * self.grid.cols() => {
+                    path.push(next);
+                    head = next;
+                }
+                _ => return vec![],
+            }
+        }
+        path
+    }
+
+    fn best_next(&self, pos: (usize, usize)) -> Option<((usize, usize), usize)> {
+        self.neighbors(pos)
+            .into_iter()
+            .filter(|&(next, _)| !self.grid.is_wall(next) && self.g[next.0][next.1] != usize::MAX)
+            .map(|(next, delta)| {
+                let cost = step_cost(self.grid.cost(next), delta) + self.g[next.0][next.1];
+                (next, cost)
+            })
+            .min_by_key(|&(_, cost)| cost)
+    }
+
+    // Works out `rhs` again, true if that left the tile inconsistent and back in the queue
+    fn update_vertex(&mut self, pos: (usize, usize)) -> bool {
+        let (row, col) = pos;
+        self.rhs[row][col] = if self.grid.is_end(pos) {
+            0
+        } else if self.grid.is_wall(pos) {
+            usize::MAX
+        } else {
+            self.best_next(pos)
+                .map(|(_, cost)| cost)
+                .unwrap_or(usize::MAX)
+        };
+
+        if self.g[row][col] != self.rhs[row][col] {
+            let key = self.key(pos);
+            self.queue.push(Reverse((key, pos)));
+            true
+        } else {
+            false
+        }
+    }
+
+    // Smallest key still worth expanding, dropping entries that went stale
+    fn top_key(&mut self) -> Option<Key> {
+        while let Some(&Reverse((key, (row, col)))) = self.queue.peek() {
+            // Already consistent, or pushed again since with a smaller key
+            if self.g[row][col] == self.rhs[row][col] || key > self.key((row, col)) {
+                self.queue.pop();
+                continue;
+            }
+            return Some(key);
+        }
+        None
+    }
+
+    fn key(&self, pos: (usize, usize)) -> Key {
+        let best = self.g[pos.0][pos.1].min(self.rhs[pos.0][pos.1]);
+        (
+            best.saturating_add(self.heuristic(self.start, pos))
+                .saturating_add(self.km),
+            best,
+        )
+    }
+
+    // Steps on the cheapest tile, diagonals counted as one step so it never overshoots
+    fn heuristic(&self, from: (usize, usize), to: (usize, usize)) -> usize {
+        let mut rows = from.0.abs_diff(to.0);
+        let mut cols = from.1.abs_diff(to.1);
+        if self.options.world_wrap_enabled {
+            rows = rows.min(self.grid.rows() - rows);
+            cols = cols.min(self.grid.cols() - cols);
+        }
+        rows.max(cols) * self.min_cost
+    }
+
+    fn neighbors(&self, pos: (usize, usize)) -> Vec<((usize, usize), (isize, isize))> {
+        self.directions
+            .iter()
+            .filter_map(|&delta| {
+                self.grid
+                    .step(pos, delta, self.options.world_wrap_enabled)
+                    .map(|next| (next, delta))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::TileType;
+    use crate::pathfinding::algorithms::{path_cost, pathfind, Algorithm};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn options(algorithm: Algorithm) -> Options {
+        Options {
+            algorithm,
+            world_wrap_enabled: false,
+            ..Options::default()
+        }
+    }
+
+    fn fresh_cost(grid: &Grid, start: (usize, usize)) -> usize {
+        path_cost(
+            grid,
+            &pathfind(grid, start, &options(Algorithm::Dijkstra)).path,
+        )
+    }
+
+    #[test]
+    fn replans_cost_the_same_as_starting_over() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut grid = Grid::new(20, 30);
+        grid.set_tile_type((10, 29), TileType::End);
+        let mut start = (10, 0);
+        let mut dstar = DStarLite::new(&grid, start, &options(Algorithm::DStarLite));
+        let tiles = [
+            TileType::Wall,
+            TileType::Open,
+            TileType::Road,
+            TileType::Mud,
+            TileType::Water,
+        ];
+        for _ in 0..300 {
+            let pos = (rng.gen_range(0..grid.rows()), rng.gen_range(0..grid.cols()));
+            if pos != start && !grid.is_end(pos) {
+                grid.set_tile_type(pos, tiles[rng.gen_range(0..tiles.len())]);
+                dstar.update_grid(&grid);
+            }
+            let path = dstar.search().path;
+            assert_eq!(path_cost(&grid, &path), fresh_cost(&grid, start));
+            // Walk a step now and then so the start moves too
+            if let Some(&next) = path.get(1).filter(|_| rng.gen_bool(0.3)) {
+                start = next;
+                dstar.move_start(start);
+            }
+        }
+    }
+
+    #[test]
+    fn a_change_off_the_path_barely_costs_anything() {
+        let mut grid = Grid::new(20, 30);
+        grid.set_tile_type((10, 29), TileType::End);
+        let mut dstar = DStarLite::new(&grid, (10, 0), &options(Algorithm::DStarLite));
+        let first = dstar.search();
+
+        grid.set_tile_type((0, 0), TileType::Wall);
+        dstar.update_grid(&grid);
+        let replan = dstar.search();
+        assert_eq!(replan.path, first.path);
+        assert!(replan.visited.len() * 10 < first.visited.len());
+    }
+
+    #[test]
+    fn walling_off_the_end_loses_the_path() {
+        let mut grid = Grid::new(5, 5);
+        grid.set_tile_type((2, 4), TileType::End);
+        let mut dstar = DStarLite::new(&grid, (2, 0), &options(Algorithm::DStarLite));
+        assert!(!dstar.search().path.is_empty());
+
+        for pos in [(1, 3), (1, 4), (2, 3), (3, 3), (3, 4)] {
+            grid.set_tile_type(pos, TileType::Wall);
+        }
+        dstar.update_grid(&grid);
+        assert!(dstar.search().path.is_empty());
+    }
+}
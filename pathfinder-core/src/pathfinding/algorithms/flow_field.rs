@@ -0,0 +1,42 @@
+use super::distance_field::distance_field;
+use crate::grid::Grid;
+
+// Straight steps first so ties don't zigzag
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, 0),
+    (0, 1),
+    (1, 0),
+    (0, -1),
+    (-1, -1),
+    (-1, 1),
+    (1, 1),
+    (1, -1),
+];
+
+/*
+ * Which way to step from every tile to get closer to the nearest end. One Dijkstra out from all
+ * the ends prices the whole grid (the integration field), then every tile points at its cheapest
+ * neighbor. Costs only ever go down along the arrows so following them always gets there, however
+ * many things are following.
+ *
+ * None on walls, ends, and anything walled off from every end.
+ */
+pub fn flow_field(grid: &Grid, world_wrap_enabled: bool) -> Vec<Vec<Option<(isize, isize)>>> {
+    let distances = distance_field(grid, &grid.ends(), world_wrap_enabled);
+    let mut directions = vec![vec![None; grid.cols()]; grid.rows()];
+    for pos in grid.positions() {
+        if grid.is_wall(pos) || grid.is_end(pos) || distances[pos.0][pos.1] == usize::MAX {
+            continue;
+        }
+        directions[pos.0][pos.1] = DIRECTIONS
+            .iter()
+            .filter_map(|&delta| {
+                let next = grid.step(pos, delta, world_wrap_enabled)?;
+                Some((delta, distances[next.0][next.1]))
+            })
+            .filter(|&(_, distance)| distance < distances[pos.0][pos.1])
+            .min_by_key(|&(_, distance)| distance)
+            .map(|(delta, _)| delta);
+    }
+    directions
+}
@@ -0,0 +1,254 @@
+use super::{
+    astar::nearest_hscore,
+    node::{nodes_from_grid, Node},
+    util::directions_for,
+    Options, SearchNode, SearchResult, SearchSide,
+};
+use crate::grid::{Grid, OPEN_COST};
+use std::collections::BinaryHeap;
+
+/*
+ * Jump Point Search is A* that skips over the boring tiles.
+ * From a node we only look in the directions that could possibly matter (pruning),
+ * then "jump" in that direction until we hit something interesting:
+ * the end, a tile with a forced neighbor (a wall corner we have to go around),
+ * or for diagonals, a tile where a straight jump finds something interesting.
+ * Only those jump points ever go into the heap, so `visited` is just the jump points.
+ * Skipping tiles only works when every tile costs the same, so JPS ignores tile weights.
+ */
+
+pub fn jps(tiles: &Grid, current_tile_pos: (usize, usize), options: &Options) -> SearchResult {
+    let mut grid = nodes_from_grid(tiles);
+    let ends = tiles.ends();
+    let mut heap = BinaryHeap::new();
+    let mut visited_order = vec![];
+    let mut path = vec![];
+    let mut reached_end = None;
+
+    // Direction we jumped to land on each node, (0, 0) for the start.
+    // Needed for pruning and to fill in the tiles between jump points for the path.
+    let mut arrived_from: Vec<Vec<(isize, isize)>> = vec![vec![(0, 0); tiles.cols()]; tiles.rows()];
+
+    let h_score = nearest_hscore(
+        tiles,
+        current_tile_pos,
+        &ends,
+        options.world_wrap_enabled,
+        false,
+        // Every step is priced like open ground here, whatever the tile
+        OPEN_COST,
+    );
+    grid[current_tile_pos.0][current_tile_pos.1].g_score = 0;
+    heap.push(Node {
+        distance: h_score,
+        g_score: 0,
+        ..grid[current_tile_pos.0][current_tile_pos.1]
+    });
+
+    let directions = directions_for(options);
+
+    while let Some(node) = heap.pop() {
+        if tiles.is_end((node.row, node.col)) {
+            reached_end = Some((node.row, node.col));
+            break;
+        }
+
+        let current_node = &mut grid[node.row][node.col];
+        if current_node.visited || node.g_score > current_node.g_score {
+            continue;
+        }
+
+        current_node.visited = true;
+
+        let pruned = pruned_directions(
+            tiles,
+            (node.row, node.col),
+            arrived_from[node.row][node.col],
+            options,
+        );
+
+        let mut opened = vec![];
+        for (dr, dc) in directions {
+            if !pruned.contains(&(dr, dc)) {
+                continue;
+            }
+
+            let Some(((jump_row, jump_col), steps)) =
+                jump(tiles, (node.row, node.col), (dr, dc), options)
+            else {
+                continue;
+            };
+
+            let directional_distance = if dr.abs() + dc.abs() == 2 { 14 } else { 10 };
+            let potential_g = node.g_score + directional_distance * steps;
+
+            let h_score = nearest_hscore(
+                tiles,
+                (jump_row, jump_col),
+                &ends,
+                options.world_wrap_enabled,
+                false,
+                OPEN_COST,
+            );
+
+            let jump_node = &mut grid[jump_row][jump_col];
+            if potential_g < jump_node.g_score {
+                jump_node.distance = potential_g + h_score;
+                jump_node.g_score = potential_g;
+                jump_node.previous_node = Some((node.row, node.col));
+                jump_node.visited = false;
+                arrived_from[jump_row][jump_col] = (dr, dc);
+                heap.push(Node { ..*jump_node });
+                opened.push((jump_row, jump_col));
+            }
+        }
+        visited_order.push(SearchNode {
+            pos: (node.row, node.col),
+            side: SearchSide::Start,
+            g_score: node.g_score,
+            previous_node: grid[node.row][node.col].previous_node,
+            opened,
+        });
+    }
+
+    // Already standing on an end, there's no jump to walk back along
+    if reached_end == Some(current_tile_pos) {
+        path.push(current_tile_pos);
+    }
+
+    // Walk back along each jump so the path covers every tile, not just the jump points
+    let mut head = reached_end.unwrap_or(current_tile_pos);
+    'jumps: while let Some(previous) = grid[head.0][head.1].previous_node {
+        let (dr, dc) = arrived_from[head.0][head.1];
+        let mut step = head;
+        while step != previous {
+            path.push(step);
+            match tiles.step(step, (-dr, -dc), options.world_wrap_enabled) {
+                Some(next) => step = next,
+                None => break 'jumps,
+            }
+        }
+        head = previous;
+        if head == current_tile_pos {
+            path.push(head);
+            break;
+        }
+    }
+    path.reverse();
+
+    SearchResult {
+        visited: visited_order,
+        path,
+    }
+}
+
+fn is_blocked(tiles: &Grid, pos: (usize, usize), delta: (isize, isize), options: &Options) -> bool {
+    tiles
+        .step(pos, delta, options.world_wrap_enabled)
+        .map(|pos| tiles.is_wall(pos))
+        .unwrap_or(true)
+}
+
+fn has_forced_neighbor(
+    tiles: &Grid,
+    pos: (usize, usize),
+    (dr, dc): (isize, isize),
+    options: &Options,
+) -> bool {
+    if dr != 0 && dc != 0 {
+        (is_blocked(tiles, pos, (-dr, 0), options) && !is_blocked(tiles, pos, (-dr, dc), options))
+            || (is_blocked(tiles, pos, (0, -dc), options)
+                && !is_blocked(tiles, pos, (dr, -dc), options))
+    } else if dr != 0 {
+        (is_blocked(tiles, pos, (0, 1), options) && !is_blocked(tiles, pos, (dr, 1), options))
+            || (is_blocked(tiles, pos, (0, -1), options)
+                && !is_blocked(tiles, pos, (dr, -1), options))
+    } else {
+        (is_blocked(tiles, pos, (1, 0), options) && !is_blocked(tiles, pos, (1, dc), options))
+            || (is_blocked(tiles, pos, (-1, 0), options)
+                && !is_blocked(tiles, pos, (-1, dc), options))
+    }
+}
+
+fn pruned_directions(
+    tiles: &Grid,
+    pos: (usize, usize),
+    (dr, dc): (isize, isize),
+    options: &Options,
+) -> Vec<(isize, isize)> {
+    if (dr, dc) == (0, 0) {
+        return vec![
+            (-1, -1),
+            (1, -1),
+            (1, 1),
+            (-1, 1),
+            (0, 1),
+            (1, 0),
+            (0, -1),
+            (-1, 0),
+        ];
+    }
+
+    let mut pruned = vec![];
+    if dr != 0 && dc != 0 {
+        pruned.extend([(dr, 0), (0, dc), (dr, dc)]);
+        if is_blocked(tiles, pos, (-dr, 0), options) {
+            pruned.push((-dr, dc));
+        }
+        if is_blocked(tiles, pos, (0, -dc), options) {
+            pruned.push((dr, -dc));
+        }
+    } else if dr != 0 {
+        pruned.push((dr, 0));
+        if is_blocked(tiles, pos, (0, 1), options) {
+            pruned.push((dr, 1));
+        }
+        if is_blocked(tiles, pos, (0, -1), options) {
+            pruned.push((dr, -1));
+        }
+    } else {
+        pruned.push((0, dc));
+        if is_blocked(tiles, pos, (1, 0), options) {
+            pruned.push((1, dc));
+        }
+        if is_blocked(tiles, pos, (-1, 0), options) {
+            pruned.push((-1, dc));
+        }
+    }
+    pruned
+}
+
+// Returns the next jump point in a direction and how many steps it took to get there
+fn jump(
+    tiles: &Grid,
+    from: (usize, usize),
+    (dr, dc): (isize, isize),
+    options: &Options,
+) -> Option<((usize, usize), usize)> {
+    let mut pos = from;
+    let mut steps = 0;
+    loop {
+        pos = tiles.step(pos, (dr, dc), options.world_wrap_enabled)?;
+        if tiles.is_wall(pos) {
+            return None;
+        }
+        steps += 1;
+
+        // Wrapped all the way around without finding anything
+        if pos == from {
+            return None;
+        }
+
+        if tiles.is_end(pos) || has_forced_neighbor(tiles, pos, (dr, dc), options) {
+            return Some((pos, steps));
+        }
+
+        if dr != 0
+            && dc != 0
+            && (jump(tiles, pos, (dr, 0), options).is_some()
+                || jump(tiles, pos, (0, dc), options).is_some())
+        {
+            return Some((pos, steps));
+        }
+    }
+}
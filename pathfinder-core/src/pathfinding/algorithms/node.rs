@@ -1,13 +1,13 @@
-use std::{cmp::Ordering, usize};
+use std::cmp::Ordering;
 
-use crate::entities::tile::Tile;
+use crate::grid::{Grid, OPEN_COST};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Node {
-    pub tile_id: usize,
     pub row: usize,
     pub col: usize,
     pub is_wall: bool,
+    pub cost: usize,
     pub distance: usize,
     pub g_score: usize,
     pub visited: bool,
@@ -28,10 +28,10 @@ impl PartialOrd for Node {
 impl Default for Node {
     fn default() -> Self {
         Node {
-            tile_id: 0,
             row: 0,
             col: 0,
             is_wall: false,
+            cost: OPEN_COST,
             distance: usize::MAX,
             g_score: usize::MAX,
             visited: false,
@@ -40,10 +40,19 @@ impl Default for Node {
     }
 }
 
-impl Node {
-    pub fn from_tile(&mut self, tile: &Tile) {
-        self.tile_id = tile.id;
-        self.row = tile.row;
-        self.col = tile.col;
+// Fresh search state for every tile, walls start out visited so nothing expands them
+pub fn nodes_from_grid(grid: &Grid) -> Vec<Vec<Node>> {
+    let mut nodes: Vec<Vec<Node>> = vec![vec![Node::default(); grid.cols()]; grid.rows()];
+    for (row, col) in grid.positions() {
+        let is_wall = grid.is_wall((row, col));
+        nodes[row][col] = Node {
+            row,
+            col,
+            is_wall,
+            visited: is_wall,
+            cost: grid.cost((row, col)),
+            ..Node::default()
+        };
     }
+    nodes
 }
@@ -0,0 +1,178 @@
+use super::{distance_field::distance_field, pathfind, Options, SearchResult};
+use crate::grid::{Grid, TileType};
+
+// Past this many ends trying every order takes too long, nearest first is close enough
+const MAX_EXACT_TOUR: usize = 8;
+
+/*
+ * Visit every end you can reach, in whatever order walks the least. Real walking costs between
+ * every pair of ends come from a distance field, then the order is brute forced (or nearest
+ * first when there are a lot). Each leg is the chosen algorithm run with only the next end in
+ * the grid, and the legs get stuck together.
+ */
+pub fn tour(grid: &Grid, start: (usize, usize), options: &Options) -> SearchResult {
+    let wrap = options.world_wrap_enabled;
+    let from_start = distance_field(grid, &[start], wrap);
+    let ends: Vec<(usize, usize)> = grid
+        .ends()
+        .into_iter()
+        .filter(|&(row, col)| from_start[row][col] != usize::MAX)
+        .collect();
+
+    // costs[i][j] from stop i to stop j, stop 0 is the start and the rest are the ends
+    let stops: Vec<(usize, usize)> = std::iter::once(start).chain(ends.iter().copied()).collect();
+    let costs: Vec<Vec<usize>> = stops
+        .iter()
+        .map(|&stop| {
+            let field = distance_field(grid, &[stop], wrap);
+            stops.iter().map(|&(row, col)| field[row][col]).collect()
+        })
+        .collect();
+
+    let order = if ends.len() <= MAX_EXACT_TOUR {
+        let mut best = (usize::MAX, vec![]);
+        let mut remaining: Vec<usize> = (1..stops.len()).collect();
+        cheapest_order(&costs, 0, &mut remaining, &mut vec![], 0, &mut best);
+        best.1
+    } else {
+        nearest_first_order(&costs)
+    };
+
+    // Each leg starts from the end the last one reached, not back on the Start tile
+    let leg_options = Options {
+        use_start_tile: false,
+        visit_all_goals: false,
+        ..options.clone()
+    };
+    let mut result = SearchResult::default();
+    let mut from = start;
+    for stop in order {
+        let to = stops[stop];
+        let mut leg_grid = grid.clone();
+        for &end in &ends {
+            if end != to {
+                leg_grid.set_tile_type(end, TileType::Open);
+            }
+        }
+        let leg = pathfind(&leg_grid, from, &leg_options);
+        result.visited.extend(leg.visited);
+        if leg.path.is_empty() {
+            break;
+        }
+        let skip = if result.path.is_empty() { 0 } else { 1 };
+        result.path.extend(leg.path.into_iter().skip(skip));
+        from = to;
+    }
+    result
+}
+
+fn cheapest_order(
+    costs: &[Vec<usize>],
+    at: usize,
+    remaining: &mut Vec<usize>,
+    order: &mut Vec<usize>,
+    cost_so_far: usize,
+    best: &mut (usize, Vec<usize>),
+) {
+    if cost_so_far >= best.0 {
+        return;
+    }
+    if remaining.is_empty() {
+        *best = (cost_so_far, order.clone());
+        return;
+    }
+    for i in 0..remaining.len() {
+        let next = remaining.swap_remove(i);
+        order.push(next);
+        let cost = cost_so_far.saturating_add(costs[at][next]);
+        cheapest_order(costs, next, remaining, order, cost, best);
+        order.pop();
+        remaining.push(next);
+        let last = remaining.len() - 1;
+        remaining.swap(i, last);
+    }
+}
+
+fn nearest_first_order(costs: &[Vec<usize>]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (1..costs.len()).collect();
+    let mut order = vec![];
+    let mut at = 0;
+    while let Some(index) = (0..remaining.len()).min_by_key(|&i| costs[at][remaining[i]]) {
+        at = remaining.swap_remove(index);
+        order.push(at);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::OPEN_COST;
+    use crate::map::parse_map;
+    use crate::pathfinding::algorithms::{path_cost, Algorithm};
+
+    fn options(algorithm: Algorithm) -> Options {
+        Options {
+            algorithm,
+            world_wrap_enabled: false,
+            visit_all_goals: true,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn goes_the_cheaper_way_round() {
+        // The end two steps right is nearest, but heading left first saves walking back over it
+        let grid = parse_map("E....E......E").unwrap().grid;
+        for algorithm in [Algorithm::Dijkstra, Algorithm::AStar, Algorithm::BFS] {
+            let path = tour(&grid, (0, 3), &options(algorithm)).path;
+            let reached: Vec<(usize, usize)> = path
+                .iter()
+                .copied()
+                .filter(|&pos| grid.is_end(pos))
+                .collect();
+            assert_eq!(reached, vec![(0, 0), (0, 5), (0, 12)], "{algorithm:?}");
+            assert_eq!(path_cost(&grid, &path), 15 * OPEN_COST, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn legs_carry_on_from_the_start_tile() {
+        let grid = parse_map("B...E.....E").unwrap().grid;
+        let options = Options {
+            use_start_tile: true,
+            ..options(Algorithm::Dijkstra)
+        };
+        let path = pathfind(&grid, (0, 7), &options).path;
+        let straight: Vec<(usize, usize)> = (0..11).map(|col| (0, col)).collect();
+        assert_eq!(path, straight);
+    }
+
+    #[test]
+    fn skips_ends_it_cant_reach() {
+        let grid = parse_map(
+            "\
+E...E
+.@@@.
+.@E@.
+.@@@.",
+        )
+        .unwrap()
+        .grid;
+        let path = tour(&grid, (3, 0), &options(Algorithm::Dijkstra)).path;
+        assert_eq!(path.first(), Some(&(3, 0)));
+        assert!(path.contains(&(0, 0)) && path.contains(&(0, 4)));
+        assert!(!path.contains(&(2, 2)));
+    }
+
+    #[test]
+    fn nearest_first_takes_the_closest_stop_each_time() {
+        let costs = vec![
+            vec![0, 30, 10, 20],
+            vec![30, 0, 20, 5],
+            vec![10, 20, 0, 15],
+            vec![20, 5, 15, 0],
+        ];
+        assert_eq!(nearest_first_order(&costs), vec![2, 3, 1]);
+    }
+}
@@ -0,0 +1,52 @@
+use super::{node::Node, Options};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+// Cost of stepping onto a node with `cost`, diagonals are ~1.4x a straight step
+pub fn step_cost(cost: usize, (dr, dc): (isize, isize)) -> usize {
+    if dr.abs() + dc.abs() == 2 {
+        cost * 14 / 10
+    } else {
+        cost
+    }
+}
+
+// The order neighbors get looked at, turned by the direction bias and shuffled by the seed
+pub fn directions_for(options: &Options) -> [(isize, isize); 8] {
+    let mut directions = [
+        (-1, -1),
+        (1, -1),
+        (1, 1),
+        (-1, 1),
+        (0, 1),
+        (1, 0),
+        (0, -1),
+        (-1, 0),
+    ];
+    directions.rotate_left(options.direction_offset);
+    if options.random_direction {
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        directions.shuffle(&mut rng);
+    }
+    directions
+}
+
+// Follow previous nodes back from the end, empty if the search never got there
+pub fn path_from_previous(
+    grid: &[Vec<Node>],
+    start: (usize, usize),
+    end: (usize, usize),
+) -> Vec<(usize, usize)> {
+    let mut path = vec![end];
+    let mut head = end;
+    while head != start {
+        match grid[head.0][head.1].previous_node {
+            Some(previous) => {
+                path.push(previous);
+                head = previous;
+            }
+            None => return vec![],
+        }
+    }
+    path.reverse();
+    path
+}
@@ -0,0 +1,199 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::grid::{Grid, TileType};
+
+pub mod braid;
+pub mod caves;
+pub mod dungeon;
+pub mod kruskals;
+pub mod node;
+pub mod prims;
+pub mod recursive_backtracker;
+pub mod recursive_division;
+mod util;
+pub mod wilsons;
+pub mod wilsons_bounded;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TerrainAlgorithm {
+    Wilsons,
+    WilsonsBounded,
+    RecursiveBacktracker,
+    Prims,
+    PrimsBounded,
+    Kruskals,
+    KruskalsBounded,
+    RecursiveDivision,
+    Caves,
+    Dungeon,
+}
+
+impl TerrainAlgorithm {
+    // Cells on even rows and cols with walls between, what braiding knows how to work with
+    pub fn is_maze(&self) -> bool {
+        !matches!(self, TerrainAlgorithm::Caves | TerrainAlgorithm::Dungeon)
+    }
+
+    pub fn world_wrap_enabled(&self) -> bool {
+        matches!(
+            self,
+            TerrainAlgorithm::Wilsons | TerrainAlgorithm::Prims | TerrainAlgorithm::Kruskals
+        )
+    }
+}
+
+// Knobs for the generators that have them
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainSettings {
+    // Chance a tile starts out as a wall before the caves get smoothed
+    pub cave_fill_ratio: f64,
+    // Smoothing passes, stops early if the caves settle first
+    pub cave_iterations: usize,
+    // How many of a maze's dead ends get knocked through afterwards, 0 keeps it perfect
+    pub braid_ratio: f64,
+}
+
+impl Default for TerrainSettings {
+    fn default() -> Self {
+        TerrainSettings {
+            cave_fill_ratio: 0.45,
+            cave_iterations: 5,
+            braid_ratio: 0.,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TerrainAction {
+    Added,
+    Removed,
+}
+
+// One tile changing, `tile_type` is what gets added or removed
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerrainStep {
+    pub pos: (usize, usize),
+    pub tile_type: TileType,
+    pub action: TerrainAction,
+}
+
+/*
+ * Leaves the finished maze in `grid` and returns every step it took to get there, in order.
+ * Split into passes for the generators that work in passes (caves), everything else is one,
+ * plus one more for braiding when that's turned on.
+ */
+pub fn generate_maze(
+    grid: &mut Grid,
+    algorithm: TerrainAlgorithm,
+    settings: &TerrainSettings,
+    seed: u64,
+) -> Vec<Vec<TerrainStep>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut passes = match algorithm {
+        TerrainAlgorithm::Wilsons => vec![wilsons::wilsons(grid, &mut rng)],
+        TerrainAlgorithm::WilsonsBounded => vec![wilsons_bounded::wilsons_bounded(grid, &mut rng)],
+        TerrainAlgorithm::RecursiveBacktracker => {
+            vec![recursive_backtracker::recursive_backtracker(grid, &mut rng)]
+        }
+        TerrainAlgorithm::Prims => vec![prims::prims(grid, &mut rng, true)],
+        TerrainAlgorithm::PrimsBounded => vec![prims::prims(grid, &mut rng, false)],
+        TerrainAlgorithm::Kruskals => vec![kruskals::kruskals(grid, &mut rng, true)],
+        TerrainAlgorithm::KruskalsBounded => vec![kruskals::kruskals(grid, &mut rng, false)],
+        TerrainAlgorithm::RecursiveDivision => {
+            vec![recursive_division::recursive_division(grid, &mut rng)]
+        }
+        TerrainAlgorithm::Caves => caves::caves(
+            grid,
+            &mut rng,
+            settings.cave_fill_ratio,
+            settings.cave_iterations,
+        ),
+        TerrainAlgorithm::Dungeon => vec![dungeon::dungeon(grid, &mut rng)],
+    };
+    for steps in &passes {
+        apply_steps(grid, steps);
+    }
+
+    if algorithm.is_maze() && settings.braid_ratio > 0. {
+        let wrap = algorithm.world_wrap_enabled();
+        let steps = braid::braid(grid, &mut rng, settings.braid_ratio, wrap);
+        apply_steps(grid, &steps);
+        passes.push(steps);
+    }
+    passes
+}
+
+pub fn apply_steps(grid: &mut Grid, steps: &[TerrainStep]) {
+    for step in steps {
+        match step.action {
+            TerrainAction::Added => grid.set_tile_type(step.pos, step.tile_type),
+            TerrainAction::Removed => grid.set_tile_type(step.pos, TileType::Open),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinding::algorithms::distance_field::distance_field;
+
+    const ALL: [TerrainAlgorithm; 10] = [
+        TerrainAlgorithm::Wilsons,
+        TerrainAlgorithm::WilsonsBounded,
+        TerrainAlgorithm::RecursiveBacktracker,
+        TerrainAlgorithm::Prims,
+        TerrainAlgorithm::PrimsBounded,
+        TerrainAlgorithm::Kruskals,
+        TerrainAlgorithm::KruskalsBounded,
+        TerrainAlgorithm::RecursiveDivision,
+        TerrainAlgorithm::Caves,
+        TerrainAlgorithm::Dungeon,
+    ];
+
+    fn generate(algorithm: TerrainAlgorithm, settings: &TerrainSettings, seed: u64) -> Grid {
+        let mut grid = Grid::new(20, 34);
+        generate_maze(&mut grid, algorithm, settings, seed);
+        grid
+    }
+
+    #[test]
+    fn same_seed_same_maze() {
+        let settings = TerrainSettings::default();
+        for algorithm in ALL {
+            let first = generate(algorithm, &settings, 7);
+            assert_eq!(first, generate(algorithm, &settings, 7), "{algorithm:?}");
+            assert_ne!(first, generate(algorithm, &settings, 8), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn steps_replay_into_the_same_grid() {
+        let settings = TerrainSettings {
+            braid_ratio: 0.5,
+            ..TerrainSettings::default()
+        };
+        for algorithm in ALL {
+            let mut grid = Grid::new(20, 34);
+            let passes = generate_maze(&mut grid, algorithm, &settings, 3);
+            let mut replayed = Grid::new(20, 34);
+            for steps in &passes {
+                apply_steps(&mut replayed, steps);
+            }
+            assert_eq!(grid, replayed, "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn mazes_leave_every_open_tile_connected() {
+        let settings = TerrainSettings::default();
+        for algorithm in ALL.into_iter().filter(|algorithm| algorithm.is_maze()) {
+            let grid = generate(algorithm, &settings, 11);
+            let open: Vec<(usize, usize)> =
+                grid.positions().filter(|&pos| !grid.is_wall(pos)).collect();
+            let field = distance_field(&grid, &open[..1], algorithm.world_wrap_enabled());
+            for (row, col) in open {
+                assert_ne!(field[row][col], usize::MAX, "{algorithm:?} at {row},{col}");
+            }
+        }
+    }
+}
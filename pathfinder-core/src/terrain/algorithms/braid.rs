@@ -0,0 +1,64 @@
+use crate::grid::{Grid, TileType};
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    util::{carve, cell_step, cells, CELL_DIRECTIONS},
+    TerrainStep,
+};
+
+/*
+ * Runs over a finished maze (cells on even rows and cols) and knocks a wall out of `ratio` of the
+ * dead ends, which makes loops. Knocking into another dead end is preferred since that fixes two
+ * at once. A dead end that got fixed by an earlier one isn't touched.
+ */
+pub fn braid<R: Rng>(
+    tiles: &Grid,
+    rng: &mut R,
+    ratio: f64,
+    world_wrap_enabled: bool,
+) -> Vec<TerrainStep> {
+    let mut grid = tiles.clone();
+    let mut terrain_events = vec![];
+
+    let mut dead_ends: Vec<(usize, usize)> = cells(&grid)
+        .filter(|&pos| is_dead_end(&grid, pos, world_wrap_enabled))
+        .collect();
+    dead_ends.shuffle(rng);
+    let to_remove = (dead_ends.len() as f64 * ratio.clamp(0., 1.)).round() as usize;
+
+    for pos in dead_ends.into_iter().take(to_remove) {
+        if !is_dead_end(&grid, pos, world_wrap_enabled) {
+            continue;
+        }
+        let mut walls: Vec<((usize, usize), (usize, usize))> = CELL_DIRECTIONS
+            .into_iter()
+            .filter_map(|direction| cell_step(&grid, pos, direction, world_wrap_enabled))
+            .filter(|&(between, next)| grid.is_wall(between) && !grid.is_wall(next))
+            .collect();
+        walls.shuffle(rng);
+        let Some(&(between, _)) = walls
+            .iter()
+            .find(|(_, next)| is_dead_end(&grid, *next, world_wrap_enabled))
+            .or(walls.first())
+        else {
+            continue;
+        };
+        grid.set_tile_type(between, TileType::Open);
+        terrain_events.push(carve(between));
+    }
+
+    terrain_events
+}
+
+// An open cell with only one way out
+fn is_dead_end(grid: &Grid, pos: (usize, usize), world_wrap_enabled: bool) -> bool {
+    if grid.is_wall(pos) {
+        return false;
+    }
+    let exits = CELL_DIRECTIONS
+        .into_iter()
+        .filter_map(|direction| cell_step(grid, pos, direction, world_wrap_enabled))
+        .filter(|&(between, _)| !grid.is_wall(between))
+        .count();
+    exits == 1
+}
@@ -0,0 +1,94 @@
+use crate::grid::{Grid, TileType};
+use rand::Rng;
+
+use super::{TerrainAction, TerrainStep};
+
+// A tile with at least this many walls around it becomes a wall
+const BIRTH_LIMIT: usize = 5;
+// A wall with at least this many walls around it stays one
+const SURVIVAL_LIMIT: usize = 4;
+
+/*
+ * Throw down walls at random, then smooth them out a few times. Every pass a tile looks at its
+ * eight neighbours (off the edge counts as wall) and turns into a wall if it's crowded, or opens
+ * up if it's lonely. The noise clumps into caves, and nothing makes sure they connect.
+ *
+ * One list of steps per pass, only the tiles that changed. End and Start never get walled over.
+ */
+pub fn caves<R: Rng>(
+    tiles: &Grid,
+    rng: &mut R,
+    fill_ratio: f64,
+    iterations: usize,
+) -> Vec<Vec<TerrainStep>> {
+    let mut walls: Vec<bool> = tiles.positions().map(|pos| tiles.is_wall(pos)).collect();
+    let index = |(row, col): (usize, usize)| row * tiles.cols() + col;
+    let stays_open =
+        |pos: (usize, usize)| matches!(tiles.tile_type(pos), TileType::End | TileType::Start);
+
+    let noise: Vec<bool> = tiles
+        .positions()
+        .map(|pos| !stays_open(pos) && rng.gen_bool(fill_ratio.clamp(0., 1.)))
+        .collect();
+    let mut passes = vec![changes(tiles, &mut walls, noise)];
+
+    for _ in 0..iterations {
+        let next: Vec<bool> = tiles
+            .positions()
+            .map(|pos| {
+                let neighbours = wall_neighbours(tiles, &walls, pos);
+                if stays_open(pos) {
+                    false
+                } else if walls[index(pos)] {
+                    neighbours >= SURVIVAL_LIMIT
+                } else {
+                    neighbours >= BIRTH_LIMIT
+                }
+            })
+            .collect();
+        let pass = changes(tiles, &mut walls, next);
+        // Settled, more passes won't do anything
+        if pass.is_empty() {
+            break;
+        }
+        passes.push(pass);
+    }
+
+    passes
+}
+
+fn wall_neighbours(tiles: &Grid, walls: &[bool], (row, col): (usize, usize)) -> usize {
+    let mut count = 0;
+    for dr in -1..=1 {
+        for dc in -1..=1 {
+            if (dr, dc) == (0, 0) {
+                continue;
+            }
+            count += match tiles.step((row, col), (dr, dc), false) {
+                Some((r, c)) => walls[r * tiles.cols() + c] as usize,
+                None => 1,
+            };
+        }
+    }
+    count
+}
+
+// Steps to go from `walls` to `next`, and `walls` becomes `next`
+fn changes(tiles: &Grid, walls: &mut Vec<bool>, next: Vec<bool>) -> Vec<TerrainStep> {
+    let steps = tiles
+        .positions()
+        .zip(walls.iter().zip(&next))
+        .filter(|(_, (was_wall, is_wall))| was_wall != is_wall)
+        .map(|(pos, (_, &is_wall))| TerrainStep {
+            pos,
+            action: if is_wall {
+                TerrainAction::Added
+            } else {
+                TerrainAction::Removed
+            },
+            tile_type: TileType::Wall,
+        })
+        .collect();
+    *walls = next;
+    steps
+}
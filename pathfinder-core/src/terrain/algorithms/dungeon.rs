@@ -0,0 +1,122 @@
+use crate::grid::{Grid, TileType};
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    util::{carve, fill_with_walls},
+    TerrainAction, TerrainStep,
+};
+
+const ROOM_ATTEMPTS: usize = 80;
+const MAX_ROOMS: usize = 12;
+const MIN_ROOM_SIZE: usize = 3;
+const MAX_ROOM_SIZE: usize = 10;
+
+// Inclusive tile bounds
+#[derive(Debug, Clone, Copy)]
+struct Room {
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+}
+
+impl Room {
+    // Touching counts, rooms keep at least a wall between them
+    fn overlaps(&self, other: &Room) -> bool {
+        self.top <= other.bottom + 1
+            && other.top <= self.bottom + 1
+            && self.left <= other.right + 1
+            && other.left <= self.right + 1
+    }
+
+    fn center(&self) -> (usize, usize) {
+        ((self.top + self.bottom) / 2, (self.left + self.right) / 2)
+    }
+}
+
+/*
+ * Fill with walls, drop rooms at random wherever they fit, then dig an L shaped corridor from
+ * each room to the one placed before it so everything connects. The End goes somewhere random
+ * in a random room. Nothing wraps, there's a wall all the way round the edge.
+ */
+pub fn dungeon<R: Rng>(tiles: &Grid, rng: &mut R) -> Vec<TerrainStep> {
+    let mut terrain_events = fill_with_walls(tiles);
+    if tiles.rows() < MIN_ROOM_SIZE + 2 || tiles.cols() < MIN_ROOM_SIZE + 2 {
+        return terrain_events;
+    }
+
+    let mut open = vec![vec![false; tiles.cols()]; tiles.rows()];
+    let mut rooms: Vec<Room> = vec![];
+    for _ in 0..ROOM_ATTEMPTS {
+        if rooms.len() == MAX_ROOMS {
+            break;
+        }
+        let height = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE.min(tiles.rows() - 2));
+        let width = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE.min(tiles.cols() - 2));
+        let top = rng.gen_range(1..=tiles.rows() - 1 - height);
+        let left = rng.gen_range(1..=tiles.cols() - 1 - width);
+        let room = Room {
+            top,
+            bottom: top + height - 1,
+            left,
+            right: left + width - 1,
+        };
+        if rooms.iter().any(|placed| placed.overlaps(&room)) {
+            continue;
+        }
+
+        for row in room.top..=room.bottom {
+            for col in room.left..=room.right {
+                dig(&mut open, &mut terrain_events, (row, col));
+            }
+        }
+        if let Some(previous) = rooms.last() {
+            let (from, to) = (previous.center(), room.center());
+            dig_corridor(rng, from, to, &mut open, &mut terrain_events);
+        }
+        rooms.push(room);
+    }
+
+    if let Some(room) = rooms.choose(rng) {
+        terrain_events.push(TerrainStep {
+            pos: (
+                rng.gen_range(room.top..=room.bottom),
+                rng.gen_range(room.left..=room.right),
+            ),
+            action: TerrainAction::Added,
+            tile_type: TileType::End,
+        });
+    }
+
+    terrain_events
+}
+
+// Along one axis then the other, coin flip for which goes first
+fn dig_corridor<R: Rng>(
+    rng: &mut R,
+    from: (usize, usize),
+    to: (usize, usize),
+    open: &mut [Vec<bool>],
+    terrain_events: &mut Vec<TerrainStep>,
+) {
+    let corner = if rng.gen_bool(0.5) {
+        (from.0, to.1)
+    } else {
+        (to.0, from.1)
+    };
+    for (start, end) in [(from, corner), (corner, to)] {
+        for row in start.0.min(end.0)..=start.0.max(end.0) {
+            for col in start.1.min(end.1)..=start.1.max(end.1) {
+                dig(open, terrain_events, (row, col));
+            }
+        }
+    }
+}
+
+// Corridors cross rooms and each other, no point animating a tile twice
+fn dig(open: &mut [Vec<bool>], terrain_events: &mut Vec<TerrainStep>, (row, col): (usize, usize)) {
+    if !open[row][col] {
+        open[row][col] = true;
+        terrain_events.push(carve((row, col)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::grid::Grid;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    util::{carve, cell_step, cells, fill_with_walls},
+    TerrainStep,
+};
+
+/*
+ * Every cell starts as its own little maze. Go through the walls between cells in a random
+ * order and knock one out whenever the cells on either side aren't connected yet, union-find
+ * keeps track of who's connected. Lots of separate pieces growing at once that eventually merge.
+ */
+pub fn kruskals<R: Rng>(tiles: &Grid, rng: &mut R, world_wrap_enabled: bool) -> Vec<TerrainStep> {
+    let mut terrain_events = fill_with_walls(tiles);
+    let index = |(row, col): (usize, usize)| row * tiles.cols() + col;
+
+    // Right and down from every cell covers every wall once
+    let mut walls = vec![];
+    for pos in cells(tiles) {
+        for direction in [(0, 2), (2, 0)] {
+            if let Some((between, next)) = cell_step(tiles, pos, direction, world_wrap_enabled) {
+                walls.push((pos, between, next));
+            }
+        }
+    }
+    walls.shuffle(rng);
+
+    let mut sets = DisjointSets::new(tiles.rows() * tiles.cols());
+    let mut carved = vec![false; tiles.rows() * tiles.cols()];
+    for (pos, between, next) in walls {
+        if !sets.union(index(pos), index(next)) {
+            continue;
+        }
+        for cell in [pos, between, next] {
+            if !carved[index(cell)] {
+                carved[index(cell)] = true;
+                terrain_events.push(carve(cell));
+            }
+        }
+    }
+
+    terrain_events
+}
+
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(size: usize) -> DisjointSets {
+        DisjointSets {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, index: usize) -> usize {
+        let mut root = index;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        // Point everything on the way straight at the root so the next find is quick
+        let mut index = index;
+        while self.parents[index] != root {
+            let next = self.parents[index];
+            self.parents[index] = root;
+            index = next;
+        }
+        root
+    }
+
+    // False when they were already joined
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        self.parents[a] = b;
+        true
+    }
+}
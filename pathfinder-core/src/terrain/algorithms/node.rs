@@ -1,8 +1,7 @@
-use crate::entities::tile::Tile;
+use crate::grid::Grid;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Node {
-    pub tile_id: usize,
     pub row: usize,
     pub col: usize,
     pub state: NodeState,
@@ -18,7 +17,6 @@ pub enum NodeState {
 impl Default for Node {
     fn default() -> Self {
         Node {
-            tile_id: 0,
             row: 0,
             col: 0,
             state: NodeState::Unvisited,
@@ -26,10 +24,11 @@ impl Default for Node {
     }
 }
 
-impl Node {
-    pub fn from_tile(&mut self, tile: &Tile) {
-        self.tile_id = tile.id;
-        self.row = tile.row;
-        self.col = tile.col;
+pub fn nodes_from_grid(grid: &Grid) -> Vec<Vec<Node>> {
+    let mut nodes: Vec<Vec<Node>> = vec![vec![Node::default(); grid.cols()]; grid.rows()];
+    for (row, col) in grid.positions() {
+        nodes[row][col].row = row;
+        nodes[row][col].col = col;
     }
+    nodes
 }
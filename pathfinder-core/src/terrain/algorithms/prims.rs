@@ -0,0 +1,55 @@
+use crate::grid::Grid;
+use rand::Rng;
+
+use super::{
+    node::{nodes_from_grid, Node, NodeState},
+    util::{carve, cell_step, fill_with_walls, random_cell, CELL_DIRECTIONS},
+    TerrainStep,
+};
+
+// A wall that could be knocked out, and the cell outside the maze behind it
+type FrontierWall = ((usize, usize), (usize, usize));
+
+/*
+ * Grow the maze out from one cell. Every wall between the maze and a cell that isn't in it yet
+ * is on the frontier, knock out a random one and add the cell behind it, then its walls join
+ * the frontier. Picking at random all over the edge makes lots of short dead ends.
+ */
+pub fn prims<R: Rng>(tiles: &Grid, rng: &mut R, world_wrap_enabled: bool) -> Vec<TerrainStep> {
+    let mut terrain_events = fill_with_walls(tiles);
+    let mut grid = nodes_from_grid(tiles);
+    let mut frontier = vec![];
+
+    let start = random_cell(tiles, rng);
+    terrain_events.push(carve(start));
+    add_to_maze(tiles, &mut grid, &mut frontier, start, world_wrap_enabled);
+
+    while !frontier.is_empty() {
+        let (between, next) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        if grid[next.0][next.1].state != NodeState::Unvisited {
+            continue;
+        }
+        terrain_events.push(carve(between));
+        terrain_events.push(carve(next));
+        add_to_maze(tiles, &mut grid, &mut frontier, next, world_wrap_enabled);
+    }
+
+    terrain_events
+}
+
+fn add_to_maze(
+    tiles: &Grid,
+    grid: &mut [Vec<Node>],
+    frontier: &mut Vec<FrontierWall>,
+    pos: (usize, usize),
+    world_wrap_enabled: bool,
+) {
+    grid[pos.0][pos.1].state = NodeState::Path;
+    for direction in CELL_DIRECTIONS {
+        if let Some((between, next)) = cell_step(tiles, pos, direction, world_wrap_enabled) {
+            if grid[next.0][next.1].state == NodeState::Unvisited {
+                frontier.push((between, next));
+            }
+        }
+    }
+}
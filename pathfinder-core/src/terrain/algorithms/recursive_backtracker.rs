@@ -0,0 +1,46 @@
+use crate::grid::Grid;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    node::{nodes_from_grid, NodeState},
+    util::{carve, cell_step, fill_with_walls, random_cell, CELL_DIRECTIONS},
+    TerrainStep,
+};
+
+/*
+ * Start everything as a wall, then dig from a random cell (even row and col) to a random
+ * unvisited neighbour two tiles over, knocking out the wall between. When there's nowhere left
+ * to go, back up until there is. Long corridors, not much branching.
+ *
+ * A stack instead of actual recursion, same order, no worrying about a bigger grid.
+ */
+pub fn recursive_backtracker<R: Rng>(tiles: &Grid, rng: &mut R) -> Vec<TerrainStep> {
+    let mut terrain_events = fill_with_walls(tiles);
+    let mut grid = nodes_from_grid(tiles);
+
+    let start = random_cell(tiles, rng);
+    grid[start.0][start.1].state = NodeState::Path;
+    terrain_events.push(carve(start));
+
+    let mut stack = vec![start];
+    while let Some(&pos) = stack.last() {
+        let unvisited: Vec<((usize, usize), (usize, usize))> = CELL_DIRECTIONS
+            .into_iter()
+            .filter_map(|direction| cell_step(tiles, pos, direction, false))
+            .filter(|&(_, (row, col))| grid[row][col].state == NodeState::Unvisited)
+            .collect();
+
+        let Some(&(between, next)) = unvisited.choose(rng) else {
+            stack.pop();
+            continue;
+        };
+
+        for (row, col) in [between, next] {
+            grid[row][col].state = NodeState::Path;
+            terrain_events.push(carve((row, col)));
+        }
+        stack.push(next);
+    }
+
+    terrain_events
+}
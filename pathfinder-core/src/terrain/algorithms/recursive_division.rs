@@ -0,0 +1,105 @@
+use crate::grid::{Grid, TileType};
+use rand::Rng;
+
+use super::{TerrainAction, TerrainStep};
+
+// Inclusive tile bounds of a chamber, corners always land on cells (even row and col)
+#[derive(Clone, Copy)]
+struct Chamber {
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+}
+
+/*
+ * The other way around from the rest, start open and add walls. Split a chamber in two with a
+ * wall on an odd row or col, leave one gap in it, then do the same to both halves until they're
+ * a cell wide. Walls go up chamber by chamber so you can watch it get cut up.
+ *
+ * The trailing odd row and col get walled off first so it's bounded like the other bounded mazes.
+ */
+pub fn recursive_division<R: Rng>(tiles: &Grid, rng: &mut R) -> Vec<TerrainStep> {
+    let mut terrain_events = vec![];
+    for pos in tiles.positions() {
+        if tiles.is_wall(pos) {
+            terrain_events.push(TerrainStep {
+                pos,
+                action: TerrainAction::Removed,
+                tile_type: TileType::Wall,
+            });
+        }
+    }
+
+    let bottom = (tiles.rows() - 1) & !1;
+    let right = (tiles.cols() - 1) & !1;
+    for pos in tiles.positions() {
+        if pos.0 > bottom || pos.1 > right {
+            terrain_events.push(wall(pos));
+        }
+    }
+
+    let mut chambers = vec![Chamber {
+        top: 0,
+        bottom,
+        left: 0,
+        right,
+    }];
+    while let Some(chamber) = chambers.pop() {
+        let height = chamber.bottom - chamber.top;
+        let width = chamber.right - chamber.left;
+        if height == 0 && width == 0 {
+            continue;
+        }
+        let horizontal = if height == width {
+            rng.gen_bool(0.5)
+        } else {
+            height > width
+        };
+
+        // Halves pushed in reverse so the top/left one gets split first
+        if horizontal {
+            let wall_row = chamber.top + rng.gen_range(0..height / 2) * 2 + 1;
+            let gap_col = chamber.left + rng.gen_range(0..=width / 2) * 2;
+            for col in chamber.left..=chamber.right {
+                if col != gap_col {
+                    terrain_events.push(wall((wall_row, col)));
+                }
+            }
+            chambers.push(Chamber {
+                top: wall_row + 1,
+                ..chamber
+            });
+            chambers.push(Chamber {
+                bottom: wall_row - 1,
+                ..chamber
+            });
+        } else {
+            let wall_col = chamber.left + rng.gen_range(0..width / 2) * 2 + 1;
+            let gap_row = chamber.top + rng.gen_range(0..=height / 2) * 2;
+            for row in chamber.top..=chamber.bottom {
+                if row != gap_row {
+                    terrain_events.push(wall((row, wall_col)));
+                }
+            }
+            chambers.push(Chamber {
+                left: wall_col + 1,
+                ..chamber
+            });
+            chambers.push(Chamber {
+                right: wall_col - 1,
+                ..chamber
+            });
+        }
+    }
+
+    terrain_events
+}
+
+fn wall(pos: (usize, usize)) -> TerrainStep {
+    TerrainStep {
+        pos,
+        action: TerrainAction::Added,
+        tile_type: TileType::Wall,
+    }
+}
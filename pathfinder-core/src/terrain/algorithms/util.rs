@@ -0,0 +1,55 @@
+use crate::grid::{Grid, TileType};
+
+use super::{TerrainAction, TerrainStep};
+
+// Cells sit on even rows and cols, the odd tiles between them are the walls that get knocked out
+pub const CELL_DIRECTIONS: [(isize, isize); 4] = [(0, 2), (2, 0), (0, -2), (-2, 0)];
+
+// Every tile turns into a wall, the mazes carve out of that
+pub fn fill_with_walls(tiles: &Grid) -> Vec<TerrainStep> {
+    tiles
+        .positions()
+        .map(|pos| TerrainStep {
+            pos,
+            action: TerrainAction::Added,
+            tile_type: TileType::Wall,
+        })
+        .collect()
+}
+
+pub fn carve(pos: (usize, usize)) -> TerrainStep {
+    TerrainStep {
+        pos,
+        action: TerrainAction::Removed,
+        tile_type: TileType::Wall,
+    }
+}
+
+pub fn random_cell<R: rand::Rng>(tiles: &Grid, rng: &mut R) -> (usize, usize) {
+    (
+        rng.gen_range(0..tiles.rows().div_ceil(2)) * 2,
+        rng.gen_range(0..tiles.cols().div_ceil(2)) * 2,
+    )
+}
+
+pub fn cells(tiles: &Grid) -> impl Iterator<Item = (usize, usize)> + '_ {
+    tiles
+        .positions()
+        .filter(|&(row, col)| row % 2 == 0 && col % 2 == 0)
+}
+
+// The wall tile in between and the cell on the other side, when there is one
+pub fn cell_step(
+    tiles: &Grid,
+    pos: (usize, usize),
+    (dr, dc): (isize, isize),
+    world_wrap_enabled: bool,
+) -> Option<((usize, usize), (usize, usize))> {
+    let between = tiles.step(pos, (dr / 2, dc / 2), world_wrap_enabled)?;
+    let next = tiles.step(pos, (dr, dc), world_wrap_enabled)?;
+    // An odd sized world wraps a cell onto a wall row, just don't go there
+    if next.0 % 2 != 0 || next.1 % 2 != 0 {
+        return None;
+    }
+    Some((between, next))
+}
@@ -1,14 +1,12 @@
-use std::usize;
+use crate::grid::{Grid, TileType};
+use rand::{seq::SliceRandom, Rng};
 
-use crate::{
-    entities::tile::{Tile, COL_COUNT, ROW_COUNT},
-    terrain::tile_modifier::{BuildType, TerrainAction, TerrainNode},
+use super::{
+    node::{nodes_from_grid, Node, NodeState},
+    TerrainAction, TerrainStep,
 };
-use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng, Rng};
 
-use super::node::{Node, NodeState};
-
-pub fn setup_and_run_wilsons(grid: &[&Tile]) -> Vec<TerrainNode> {
+pub fn wilsons<R: Rng>(tiles: &Grid, rng: &mut R) -> Vec<TerrainStep> {
     /*
      * Create a terrain event to convert every Tile to a wall
      *
@@ -19,45 +17,44 @@ pub fn setup_and_run_wilsons(grid: &[&Tile]) -> Vec<TerrainNode> {
      * I need the tile ids, so maybe I just do the whole grid with modified access.
      */
     let mut terrain_events = vec![];
-    let mut nodes: Vec<Vec<Node>> = vec![vec![Node::default(); COL_COUNT]; ROW_COUNT];
-    for tile in grid {
-        terrain_events.push(TerrainNode {
-            tile_id: tile.id,
+    for pos in tiles.positions() {
+        terrain_events.push(TerrainStep {
+            pos,
             action: TerrainAction::Added,
-            build_type: BuildType::Wall,
+            tile_type: TileType::Wall,
         });
-        nodes[tile.row][tile.col].from_tile(tile);
     }
-    wilsons(nodes, &mut terrain_events);
+    carve(nodes_from_grid(tiles), rng, &mut terrain_events);
     terrain_events
 }
 
-pub fn wilsons(mut grid: Vec<Vec<Node>>, terrain_events: &mut Vec<TerrainNode>) {
-    let mut rng = thread_rng();
+fn carve<R: Rng>(mut grid: Vec<Vec<Node>>, rng: &mut R, terrain_events: &mut Vec<TerrainStep>) {
+    let rows = grid.len();
+    let cols = grid[0].len();
 
-    let seed_row = rng.gen_range(0..ROW_COUNT / 2) * 2;
-    let seed_col = rng.gen_range(0..COL_COUNT / 2) * 2;
+    let seed_row = rng.gen_range(0..rows / 2) * 2;
+    let seed_col = rng.gen_range(0..cols / 2) * 2;
     grid[seed_row][seed_col].state = NodeState::Path;
-    terrain_events.push(TerrainNode {
-        tile_id: grid[seed_row][seed_col].tile_id,
+    terrain_events.push(TerrainStep {
+        pos: (seed_row, seed_col),
         action: TerrainAction::Removed,
-        build_type: BuildType::Wall,
+        tile_type: TileType::Wall,
     });
 
-    while let Some((row, col)) = pick_random_unvisited(&grid, &mut rng) {
-        let path = random_walk(row, col, &mut grid, &mut rng, terrain_events);
+    while let Some((row, col)) = pick_random_unvisited(&grid, rng) {
+        let path = random_walk(row, col, &mut grid, rng, terrain_events);
 
         for &(r, c) in &path {
             grid[r][c].state = NodeState::Path;
-            terrain_events.push(TerrainNode {
-                tile_id: grid[r][c].tile_id,
+            terrain_events.push(TerrainStep {
+                pos: (r, c),
                 action: TerrainAction::Removed,
-                build_type: BuildType::Wall,
+                tile_type: TileType::Wall,
             });
         }
     }
 }
-fn pick_random_unvisited(grid: &Vec<Vec<Node>>, rng: &mut ThreadRng) -> Option<(usize, usize)> {
+fn pick_random_unvisited<R: Rng>(grid: &[Vec<Node>], rng: &mut R) -> Option<(usize, usize)> {
     let unvisited_nodes: Vec<(usize, usize)> = grid
         .iter()
         .flat_map(|row| row.iter())
@@ -72,26 +69,28 @@ fn pick_random_unvisited(grid: &Vec<Vec<Node>>, rng: &mut ThreadRng) -> Option<(
     }
 }
 
-fn random_walk(
+fn random_walk<R: Rng>(
     start_row: usize,
     start_col: usize,
-    grid: &mut Vec<Vec<Node>>,
-    rng: &mut ThreadRng,
-    terrain_events: &mut Vec<TerrainNode>,
+    grid: &mut [Vec<Node>],
+    rng: &mut R,
+    terrain_events: &mut Vec<TerrainStep>,
 ) -> Vec<(usize, usize)> {
     let directions = [(0, 2), (2, 0), (0, -2), (-2, 0)];
+    let rows = grid.len();
+    let cols = grid[0].len();
     let in_bounds = |row: isize, col: isize| -> bool {
-        row >= 0 && row < ROW_COUNT as isize && col >= 0 && col < COL_COUNT as isize
+        row >= 0 && row < rows as isize && col >= 0 && col < cols as isize
     };
 
     let mut current_row = start_row;
     let mut current_col = start_col;
     let mut path: Vec<(usize, usize)> = vec![(start_row, start_col)];
     grid[current_row][current_col].state = NodeState::Current;
-    terrain_events.push(TerrainNode {
-        tile_id: grid[current_row][current_col].tile_id,
+    terrain_events.push(TerrainStep {
+        pos: (current_row, current_col),
         action: TerrainAction::Removed,
-        build_type: BuildType::Wall,
+        tile_type: TileType::Wall,
     });
 
     let mut last_direction: (isize, isize) = (0, 0);
@@ -106,30 +105,30 @@ fn random_walk(
         // let mut new_col = current_col as isize + dc;
         //
         // world wrap
-        let new_row = (((current_row + ROW_COUNT) as isize + dr) as usize % ROW_COUNT) as isize; // add row count to avoid negative index >.> <.<
-        let new_col = (((current_col + COL_COUNT) as isize + dc) as usize % COL_COUNT) as isize;
+        let new_row = (((current_row + rows) as isize + dr) as usize % rows) as isize; // add row count to avoid negative index >.> <.<
+        let new_col = (((current_col + cols) as isize + dc) as usize % cols) as isize;
         if in_bounds(new_row, new_col) {
             last_direction = (-dr, -dc);
             let u_new_row = new_row as usize;
             let u_new_col = new_col as usize;
 
-            let intermediate_row = ((new_row - dr / 2) + ROW_COUNT as isize) as usize % ROW_COUNT;
-            let intermediate_col = ((new_col - dc / 2) + COL_COUNT as isize) as usize % COL_COUNT;
+            let intermediate_row = ((new_row - dr / 2) + rows as isize) as usize % rows;
+            let intermediate_col = ((new_col - dc / 2) + cols as isize) as usize % cols;
             // flip the one behind you
             let intermediate_node = &mut grid[intermediate_row][intermediate_col];
             intermediate_node.state = NodeState::Current;
             path.push((intermediate_row, intermediate_col));
-            terrain_events.push(TerrainNode {
-                tile_id: intermediate_node.tile_id,
+            terrain_events.push(TerrainStep {
+                pos: (intermediate_node.row, intermediate_node.col),
                 action: TerrainAction::Removed,
-                build_type: BuildType::Wall,
+                tile_type: TileType::Wall,
             });
             if grid[u_new_row][u_new_col].state == NodeState::Unvisited {
                 grid[u_new_row][u_new_col].state = NodeState::Current;
-                terrain_events.push(TerrainNode {
-                    tile_id: grid[u_new_row][u_new_col].tile_id,
+                terrain_events.push(TerrainStep {
+                    pos: (u_new_row, u_new_col),
                     action: TerrainAction::Removed,
-                    build_type: BuildType::Wall,
+                    tile_type: TileType::Wall,
                 });
                 path.push((u_new_row, u_new_col));
                 current_row = u_new_row;
@@ -144,10 +143,10 @@ fn random_walk(
                         continue;
                     }
                     grid[*row][*col].state = NodeState::Unvisited;
-                    terrain_events.push(TerrainNode {
-                        tile_id: grid[*row][*col].tile_id,
+                    terrain_events.push(TerrainStep {
+                        pos: (*row, *col),
                         action: TerrainAction::Added,
-                        build_type: BuildType::Wall,
+                        tile_type: TileType::Wall,
                     });
                 }
                 path.truncate(split_vec_idx.unwrap() + 1); // Skip intermediate for 2 block jumps
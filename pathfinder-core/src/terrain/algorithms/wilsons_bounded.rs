@@ -1,14 +1,12 @@
-use std::usize;
+use crate::grid::{Grid, TileType};
+use rand::{seq::SliceRandom, Rng};
 
-use crate::{
-    entities::tile::{Tile, COL_COUNT, ROW_COUNT},
-    terrain::tile_modifier::{BuildType, TerrainAction, TerrainNode},
+use super::{
+    node::{nodes_from_grid, Node, NodeState},
+    TerrainAction, TerrainStep,
 };
-use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng, Rng};
 
-use super::node::{Node, NodeState};
-
-pub fn setup_and_run_wilsons_bounded(grid: &[&Tile]) -> Vec<TerrainNode> {
+pub fn wilsons_bounded<R: Rng>(tiles: &Grid, rng: &mut R) -> Vec<TerrainStep> {
     /*
      * Create a terrain event to convert every Tile to a wall
      *
@@ -19,45 +17,44 @@ pub fn setup_and_run_wilsons_bounded(grid: &[&Tile]) -> Vec<TerrainNode> {
      * I need the tile ids, so maybe I just do the whole grid with modified access.
      */
     let mut terrain_events = vec![];
-    let mut nodes: Vec<Vec<Node>> = vec![vec![Node::default(); COL_COUNT]; ROW_COUNT];
-    for tile in grid {
-        terrain_events.push(TerrainNode {
-            tile_id: tile.id,
+    for pos in tiles.positions() {
+        terrain_events.push(TerrainStep {
+            pos,
             action: TerrainAction::Added,
-            build_type: BuildType::Wall,
+            tile_type: TileType::Wall,
         });
-        nodes[tile.row][tile.col].from_tile(tile);
     }
-    wilsons_bounded(nodes, &mut terrain_events);
+    carve(nodes_from_grid(tiles), rng, &mut terrain_events);
     terrain_events
 }
 
-pub fn wilsons_bounded(mut grid: Vec<Vec<Node>>, terrain_events: &mut Vec<TerrainNode>) {
-    let mut rng = thread_rng();
+fn carve<R: Rng>(mut grid: Vec<Vec<Node>>, rng: &mut R, terrain_events: &mut Vec<TerrainStep>) {
+    let rows = grid.len();
+    let cols = grid[0].len();
 
-    let seed_row = rng.gen_range(0..ROW_COUNT / 2) * 2;
-    let seed_col = rng.gen_range(0..COL_COUNT / 2) * 2;
+    let seed_row = rng.gen_range(0..rows / 2) * 2;
+    let seed_col = rng.gen_range(0..cols / 2) * 2;
     grid[seed_row][seed_col].state = NodeState::Path;
-    terrain_events.push(TerrainNode {
-        tile_id: grid[seed_row][seed_col].tile_id,
+    terrain_events.push(TerrainStep {
+        pos: (seed_row, seed_col),
         action: TerrainAction::Removed,
-        build_type: BuildType::Wall,
+        tile_type: TileType::Wall,
     });
 
-    while let Some((row, col)) = pick_random_unvisited(&grid, &mut rng) {
-        let path = random_walk(row, col, &mut grid, &mut rng, terrain_events);
+    while let Some((row, col)) = pick_random_unvisited(&grid, rng) {
+        let path = random_walk(row, col, &mut grid, rng, terrain_events);
 
         for &(r, c) in &path {
             grid[r][c].state = NodeState::Path;
-            terrain_events.push(TerrainNode {
-                tile_id: grid[r][c].tile_id,
+            terrain_events.push(TerrainStep {
+                pos: (r, c),
                 action: TerrainAction::Removed,
-                build_type: BuildType::Wall,
+                tile_type: TileType::Wall,
             });
         }
     }
 }
-fn pick_random_unvisited(grid: &Vec<Vec<Node>>, rng: &mut ThreadRng) -> Option<(usize, usize)> {
+fn pick_random_unvisited<R: Rng>(grid: &[Vec<Node>], rng: &mut R) -> Option<(usize, usize)> {
     let unvisited_nodes: Vec<(usize, usize)> = grid
         .iter()
         .flat_map(|row| row.iter())
@@ -72,26 +69,28 @@ fn pick_random_unvisited(grid: &Vec<Vec<Node>>, rng: &mut ThreadRng) -> Option<(
     }
 }
 
-fn random_walk(
+fn random_walk<R: Rng>(
     start_row: usize,
     start_col: usize,
-    grid: &mut Vec<Vec<Node>>,
-    rng: &mut ThreadRng,
-    terrain_events: &mut Vec<TerrainNode>,
+    grid: &mut [Vec<Node>],
+    rng: &mut R,
+    terrain_events: &mut Vec<TerrainStep>,
 ) -> Vec<(usize, usize)> {
     let directions = [(0, 2), (2, 0), (0, -2), (-2, 0)];
+    let rows = grid.len();
+    let cols = grid[0].len();
     let in_bounds = |row: isize, col: isize| -> bool {
-        row >= 0 && row < ROW_COUNT as isize && col >= 0 && col < COL_COUNT as isize
+        row >= 0 && row < rows as isize && col >= 0 && col < cols as isize
     };
 
     let mut current_row = start_row;
     let mut current_col = start_col;
     let mut path: Vec<(usize, usize)> = vec![(start_row, start_col)];
     grid[current_row][current_col].state = NodeState::Current;
-    terrain_events.push(TerrainNode {
-        tile_id: grid[current_row][current_col].tile_id,
+    terrain_events.push(TerrainStep {
+        pos: (current_row, current_col),
         action: TerrainAction::Removed,
-        build_type: BuildType::Wall,
+        tile_type: TileType::Wall,
     });
 
     let mut last_direction: (isize, isize) = (0, 0);
@@ -113,17 +112,17 @@ fn random_walk(
                 &mut grid[(new_row - dr / 2) as usize][(new_col - dc / 2) as usize];
             intermediate_node.state = NodeState::Current;
             path.push((intermediate_node.row, intermediate_node.col));
-            terrain_events.push(TerrainNode {
-                tile_id: intermediate_node.tile_id,
+            terrain_events.push(TerrainStep {
+                pos: (intermediate_node.row, intermediate_node.col),
                 action: TerrainAction::Removed,
-                build_type: BuildType::Wall,
+                tile_type: TileType::Wall,
             });
             if grid[u_new_row][u_new_col].state == NodeState::Unvisited {
                 grid[u_new_row][u_new_col].state = NodeState::Current;
-                terrain_events.push(TerrainNode {
-                    tile_id: grid[u_new_row][u_new_col].tile_id,
+                terrain_events.push(TerrainStep {
+                    pos: (u_new_row, u_new_col),
                     action: TerrainAction::Removed,
-                    build_type: BuildType::Wall,
+                    tile_type: TileType::Wall,
                 });
                 path.push((u_new_row, u_new_col));
                 current_row = u_new_row;
@@ -138,10 +137,10 @@ fn random_walk(
                         continue;
                     }
                     grid[*row][*col].state = NodeState::Unvisited;
-                    terrain_events.push(TerrainNode {
-                        tile_id: grid[*row][*col].tile_id,
+                    terrain_events.push(TerrainStep {
+                        pos: (*row, *col),
                         action: TerrainAction::Added,
-                        build_type: BuildType::Wall,
+                        tile_type: TileType::Wall,
                     });
                 }
                 path.truncate(split_vec_idx.unwrap() + 1); // Skip intermediate for 2 block jumps
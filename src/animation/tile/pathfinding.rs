@@ -1,14 +1,31 @@
 use bevy::prelude::*;
 
 use super::{TileAnimation, TileAnimationState};
+use crate::input::{InputAction, KeyboardInputEvent};
 use crate::{
-    entities::tile::Tile,
-    pathfinding::emit_pathfinding::{PathEvent, PathfindingEvent, PathfindingNode},
+    entities::tile::{Tile, COL_COUNT, ROW_COUNT},
+    pathfinding::emit_pathfinding::{PathEvent, PathfindingEvent, SearchSide},
 };
-use std::{collections::VecDeque, sync::atomic::AtomicUsize, time::Duration};
+use std::{collections::HashMap, sync::atomic::AtomicUsize, time::Duration};
 
 const PATHFINDING_ANIMATION_DELAY_MS: u64 = 16;
-const PATHFINDING_TILE_BATCH: u64 = 5;
+const PATHFINDING_TILE_BATCH: usize = 5;
+// Speeding up past this shrinks the delay instead, slowing down past it grows the delay
+const MIN_ANIMATION_DELAY_MS: u64 = 16;
+const MAX_ANIMATION_DELAY_MS: u64 = 2048;
+const MAX_TILE_BATCH: usize = 640;
+// Page Up/Down jump this fraction of the search
+const SCRUB_FRACTION: f32 = 0.1;
+// Hue shift for nodes expanded from the end tile, opposite side of the color wheel
+const END_SIDE_HUE_OFFSET: usize = 180;
+// Hue shift for tiles sitting on the frontier, a quarter turn from the closed ones
+const FRONTIER_HUE_OFFSET: usize = 90;
+// Heatmap runs blue at the start to red at the farthest tile the search expanded
+const HEATMAP_NEAR_HUE: usize = 240;
+const HEATMAP_FAR_HUE: usize = 0;
+// Arrows stop short of the parent's center so the heads don't pile up on it
+const TREE_ARROW_REACH: f32 = 0.75;
+const TREE_ARROW_COLOR: Color = Color::srgba(1., 1., 1., 0.6);
 
 pub struct PathfindingTileAnimationPlugin;
 
@@ -21,19 +38,111 @@ impl Plugin for PathfindingTileAnimationPlugin {
             ),
             event_queues: Vec::new(),
         })
-        .add_systems(Update, (initiate_animation, handle_pathfinding_event));
+        .insert_resource(Playback::default())
+        .insert_resource(SearchPaint {
+            show_frontier: true,
+            heatmap: false,
+            show_tree: false,
+        })
+        .add_systems(
+            Update,
+            (
+                initiate_animation,
+                handle_pathfinding_event,
+                set_playback_from_keyboard_input,
+                set_search_paint_from_keyboard_input,
+                draw_search_tree,
+            ),
+        );
     }
 }
 
+/*
+ * How the searches play back. Steps and scrubs only move the newest search (the one you're
+ * looking at, or every search in a comparison), anything older just finishes playing when
+ * unpaused.
+ */
+#[derive(Resource)]
+pub struct Playback {
+    pub paused: bool,
+    pub delay_ms: u64,
+    pub steps_per_tick: usize,
+    // Nodes to move the newest search by, negative rewinds, picked up on the next frame
+    pub pending_steps: isize,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Playback {
+            paused: false,
+            delay_ms: PATHFINDING_ANIMATION_DELAY_MS,
+            steps_per_tick: PATHFINDING_TILE_BATCH,
+            pending_steps: 0,
+        }
+    }
+}
+
+impl Playback {
+    fn speed_up(&mut self) {
+        if self.delay_ms > MIN_ANIMATION_DELAY_MS {
+            self.delay_ms = (self.delay_ms / 2).max(MIN_ANIMATION_DELAY_MS);
+        } else {
+            self.steps_per_tick = (self.steps_per_tick * 2).min(MAX_TILE_BATCH);
+        }
+    }
+
+    fn slow_down(&mut self) {
+        if self.steps_per_tick > 1 {
+            self.steps_per_tick /= 2;
+        } else {
+            self.delay_ms = (self.delay_ms * 2).min(MAX_ANIMATION_DELAY_MS);
+        }
+    }
+}
+
+// What gets painted for a search, picked when the search is sent off to animate
+#[derive(Resource)]
+pub struct SearchPaint {
+    // Paint tiles as they join the frontier (open set) too, not just once they're closed
+    pub show_frontier: bool,
+    // Color expanded tiles by their g-score instead of one hue per search, frontier isn't painted
+    pub heatmap: bool,
+    // Arrow from every expanded tile to its parent for the search being shown
+    pub show_tree: bool,
+}
+
 #[derive(Resource)]
 struct PathfindingAnimationGate {
     timer: Timer,
-    event_queues: Vec<VecDeque<AnimationFromPathfinding>>,
+    event_queues: Vec<AnimationQueue>,
+}
+
+// Everything stays in the queue so it can be rewound, `cursor` is how much has been painted
+#[derive(Default)]
+struct AnimationQueue {
+    animations: Vec<AnimationFromPathfinding>,
+    cursor: usize,
+    // Queues sent in the same frame (a comparison) share a batch and step together
+    batch: usize,
 }
 
+// The newest batch is what stepping, scrubbing and the tree arrows work on
+fn latest_batch(event_queues: &[AnimationQueue]) -> Option<usize> {
+    event_queues.last().map(|queue| queue.batch)
+}
+
+// One expansion, the closed tile and whatever it put on the frontier, stepped as a unit
 struct AnimationFromPathfinding {
-    event: PathfindingNode,
+    paints: Vec<TilePaint>,
+    // Expanded tile and its parent, for drawing the search tree
+    tree_edge: Option<(usize, usize)>,
+}
+
+struct TilePaint {
+    tile_id: usize,
     color: usize,
+    // The tile's hue before this painted over it, None if it wasn't showing, for stepping back
+    painted_over: Option<usize>,
 }
 
 static COUNTER: AtomicUsize = AtomicUsize::new(1);
@@ -41,58 +150,327 @@ fn get_calc_number() -> usize {
     COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst)
 }
 
+type TileAnimationItems<'a> = (
+    Mut<'a, TileAnimation>,
+    Mut<'a, Visibility>,
+    Mut<'a, Transform>,
+);
+
 fn initiate_animation(
-    mut anim_states: Query<(&Tile, &mut TileAnimation)>,
+    mut anim_states: Query<(&Tile, &mut TileAnimation, &mut Visibility, &mut Transform)>,
     mut animation_gate: ResMut<PathfindingAnimationGate>,
+    mut playback: ResMut<Playback>,
     time: Res<Time>,
 ) {
+    let delay = Duration::from_millis(playback.delay_ms);
+    if animation_gate.timer.duration() != delay {
+        animation_gate.timer.set_duration(delay);
+    }
     animation_gate.timer.tick(time.delta());
 
-    if animation_gate.timer.finished() {
+    let playing = !playback.paused && animation_gate.timer.finished();
+    if !playing && playback.pending_steps == 0 {
+        return;
+    }
+
+    let mut tiles: HashMap<usize, TileAnimationItems> = anim_states
+        .iter_mut()
+        .map(|(tile, anim, vis, xf)| (tile.id, (anim, vis, xf)))
+        .collect();
+
+    if playback.pending_steps != 0 {
+        let steps = std::mem::take(&mut playback.pending_steps);
+        let batch = latest_batch(&animation_gate.event_queues);
+        let latest_batch = animation_gate
+            .event_queues
+            .iter_mut()
+            .filter(|queue| Some(queue.batch) == batch);
+        for event_queue in latest_batch {
+            if steps > 0 {
+                for _ in 0..steps.unsigned_abs() {
+                    if !paint_next(event_queue, &mut tiles) {
+                        break;
+                    }
+                }
+            } else {
+                for _ in 0..steps.unsigned_abs() {
+                    if !unpaint_last(event_queue, &mut tiles) {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    if playing {
         get_calc_number();
         for event_queue in &mut animation_gate.event_queues {
-            for _ in 0..PATHFINDING_TILE_BATCH {
-                if let Some(event) = event_queue.pop_front() {
-                    for (tile, mut anim) in &mut anim_states {
-                        if tile.id == event.event.tile_id {
-                            anim.update_color = true;
-                            anim.color = event.color;
-                            if anim.state == TileAnimationState::Ran {
-                                anim.state = TileAnimationState::Initiated;
-                            }
-                        }
-                    }
+            for _ in 0..playback.steps_per_tick {
+                if !paint_next(event_queue, &mut tiles) {
+                    break;
                 }
             }
         }
     }
 }
 
+// False once there's nothing left to paint
+fn paint_next(
+    event_queue: &mut AnimationQueue,
+    tiles: &mut HashMap<usize, TileAnimationItems>,
+) -> bool {
+    let Some(animation) = event_queue.animations.get_mut(event_queue.cursor) else {
+        return false;
+    };
+    event_queue.cursor += 1;
+
+    for paint in &mut animation.paints {
+        let Some((anim, vis, _xf)) = tiles.get_mut(&paint.tile_id) else {
+            continue;
+        };
+        if anim.state == TileAnimationState::Disabled {
+            continue;
+        }
+        paint.painted_over = (**vis != Visibility::Hidden).then_some(anim.color);
+        anim.update_color = true;
+        anim.color = paint.color;
+        if anim.state == TileAnimationState::Ran {
+            anim.state = TileAnimationState::Initiated;
+        }
+    }
+    true
+}
+
+// False once we're back at the start
+fn unpaint_last(
+    event_queue: &mut AnimationQueue,
+    tiles: &mut HashMap<usize, TileAnimationItems>,
+) -> bool {
+    if event_queue.cursor == 0 {
+        return false;
+    }
+    event_queue.cursor -= 1;
+    let animation = &event_queue.animations[event_queue.cursor];
+
+    for paint in animation.paints.iter().rev() {
+        let Some((anim, vis, xf)) = tiles.get_mut(&paint.tile_id) else {
+            continue;
+        };
+        if anim.state == TileAnimationState::Disabled {
+            continue;
+        }
+        match paint.painted_over {
+            Some(color) => {
+                anim.update_color = true;
+                anim.color = color;
+                anim.state = TileAnimationState::Initiated;
+            }
+            // Walls and weighted tiles keep showing their own color
+            None if anim.super_color.is_none() => {
+                anim.state = TileAnimationState::Ran;
+                xf.scale = Vec3::ONE;
+                **vis = Visibility::Hidden;
+            }
+            None => {}
+        }
+    }
+    true
+}
+
 fn handle_pathfinding_event(
     mut pathfinding_event_reader: EventReader<PathfindingEvent>,
     mut path_event_reader: EventReader<PathEvent>,
     mut animation_gate: ResMut<PathfindingAnimationGate>,
+    search_paint: Res<SearchPaint>,
 ) {
-    let mut new_animation = VecDeque::default();
-    let color = get_calc_number();
+    let batch = get_calc_number();
+    let mut new_queues = vec![];
+    let paint = |tile_id, color| TilePaint {
+        tile_id,
+        color,
+        painted_over: None,
+    };
     for event in pathfinding_event_reader.read() {
+        let mut new_animation = AnimationQueue { batch, ..default() };
+        let color = event.hue.unwrap_or(batch);
+        // A heatmap would wipe out the hues telling compared searches apart
+        let heatmap = search_paint.heatmap && event.hue.is_none();
+        let max_g_score = event
+            .visited
+            .iter()
+            .map(|node| node.g_score)
+            // D* Lite expands tiles that were just cut off, they show up as the far end
+            .filter(|&g_score| g_score != usize::MAX)
+            .max()
+            .unwrap_or(0);
         for node in &event.visited {
-            new_animation.push_back(AnimationFromPathfinding {
-                event: node.clone(),
-                color,
-            });
+            let tree_edge = node
+                .previous_tile_id
+                .map(|previous_tile_id| (node.tile_id, previous_tile_id));
+            if heatmap {
+                new_animation.animations.push(AnimationFromPathfinding {
+                    paints: vec![paint(node.tile_id, heat_hue(node.g_score, max_g_score))],
+                    tree_edge,
+                });
+                continue;
+            }
+            let color = match node.side {
+                SearchSide::Start => color,
+                SearchSide::End => color + END_SIDE_HUE_OFFSET,
+            };
+            let mut paints = vec![paint(node.tile_id, color)];
+            if search_paint.show_frontier {
+                paints.extend(
+                    node.opened
+                        .iter()
+                        .map(|&tile_id| paint(tile_id, color + FRONTIER_HUE_OFFSET)),
+                );
+            }
+            new_animation
+                .animations
+                .push(AnimationFromPathfinding { paints, tree_edge });
         }
+        new_queues.push(new_animation);
     }
     for event in path_event_reader.read() {
-        for node in &event.nodes {
-            new_animation.push_back(AnimationFromPathfinding {
-                event: node.clone(),
-                color,
-            })
+        let color = event.hue.unwrap_or(batch);
+        new_queues.push(AnimationQueue {
+            batch,
+            animations: event
+                .nodes
+                .iter()
+                .map(|node| AnimationFromPathfinding {
+                    paints: vec![paint(node.tile_id, color)],
+                    tree_edge: None,
+                })
+                .collect(),
+            ..default()
+        });
+    }
+
+    new_queues.retain(|queue| !queue.animations.is_empty());
+    if !new_queues.is_empty() {
+        // Finished ones can't be stepped anymore, no need to hang on to them
+        animation_gate
+            .event_queues
+            .retain(|queue| queue.cursor < queue.animations.len());
+        animation_gate.event_queues.extend(new_queues);
+    }
+}
+
+// Follows playback, only the part of the newest search painted so far gets arrows
+fn draw_search_tree(
+    mut gizmos: Gizmos,
+    tiles: Query<(&Tile, &GlobalTransform)>,
+    animation_gate: Res<PathfindingAnimationGate>,
+    search_paint: Res<SearchPaint>,
+) {
+    if !search_paint.show_tree {
+        return;
+    }
+    let batch = latest_batch(&animation_gate.event_queues);
+    let painted = animation_gate
+        .event_queues
+        .iter()
+        .filter(|queue| Some(queue.batch) == batch)
+        .flat_map(|queue| &queue.animations[..queue.cursor]);
+
+    let tiles: HashMap<usize, (&Tile, Vec2)> = tiles
+        .iter()
+        .map(|(tile, xf)| (tile.id, (tile, xf.translation().truncate())))
+        .collect();
+    for animation in painted {
+        let Some((tile_id, previous_tile_id)) = animation.tree_edge else {
+            continue;
+        };
+        let (Some((tile, from)), Some((previous_tile, to))) =
+            (tiles.get(&tile_id), tiles.get(&previous_tile_id))
+        else {
+            continue;
+        };
+        // Parents across the world wrap would draw a line over the whole grid, leave them out
+        if tile.row.abs_diff(previous_tile.row) > ROW_COUNT / 2
+            || tile.col.abs_diff(previous_tile.col) > COL_COUNT / 2
+        {
+            continue;
         }
+        gizmos.arrow_2d(*from, from.lerp(*to, TREE_ARROW_REACH), TREE_ARROW_COLOR);
+    }
+}
+
+fn heat_hue(g_score: usize, max_g_score: usize) -> usize {
+    if max_g_score == 0 {
+        return HEATMAP_NEAR_HUE;
     }
+    let heat = (g_score as f32 / max_g_score as f32).min(1.);
+    HEATMAP_NEAR_HUE - ((HEATMAP_NEAR_HUE - HEATMAP_FAR_HUE) as f32 * heat) as usize
+}
+
+fn set_search_paint_from_keyboard_input(
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut search_paint: ResMut<SearchPaint>,
+) {
+    for keyboard_input in keyboard_input_reader.read() {
+        if keyboard_input.action != InputAction::Pressed {
+            continue;
+        }
+        match keyboard_input.key {
+            KeyCode::Slash => search_paint.show_frontier = !search_paint.show_frontier,
+            KeyCode::Backslash => search_paint.heatmap = !search_paint.heatmap,
+            KeyCode::Backquote => search_paint.show_tree = !search_paint.show_tree,
+            _ => {}
+        }
+    }
+}
 
-    if new_animation.len() != 0 {
-        animation_gate.event_queues.push(new_animation);
+fn set_playback_from_keyboard_input(
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut playback: ResMut<Playback>,
+    animation_gate: Res<PathfindingAnimationGate>,
+) {
+    for keyboard_input in keyboard_input_reader.read() {
+        if keyboard_input.action != InputAction::Pressed {
+            continue;
+        }
+        let batch = latest_batch(&animation_gate.event_queues);
+        let search_len = animation_gate
+            .event_queues
+            .iter()
+            .filter(|queue| Some(queue.batch) == batch)
+            .map(|queue| queue.animations.len())
+            .max()
+            .unwrap_or(0);
+        let scrub = ((search_len as f32 * SCRUB_FRACTION).ceil() as isize).max(1);
+
+        match keyboard_input.key {
+            KeyCode::Space => playback.paused = !playback.paused,
+            KeyCode::ArrowUp => playback.speed_up(),
+            KeyCode::ArrowDown => playback.slow_down(),
+            KeyCode::ArrowRight => {
+                playback.paused = true;
+                playback.pending_steps += 1;
+            }
+            KeyCode::ArrowLeft => {
+                playback.paused = true;
+                playback.pending_steps -= 1;
+            }
+            KeyCode::PageDown => {
+                playback.paused = true;
+                playback.pending_steps += scrub;
+            }
+            KeyCode::PageUp => {
+                playback.paused = true;
+                playback.pending_steps -= scrub;
+            }
+            KeyCode::Home => {
+                playback.paused = true;
+                playback.pending_steps = -(search_len as isize);
+            }
+            KeyCode::End => {
+                playback.paused = true;
+                playback.pending_steps = search_len as isize;
+            }
+            _ => {}
+        }
     }
 }
@@ -3,8 +3,11 @@ use bevy::prelude::*;
 use super::{TileAnimation, TileAnimationState};
 use crate::input::{InputAction, KeyboardInputEvent};
 use crate::{
-    entities::tile::{EndUpdatedEvent, Tile, COL_COUNT, END_TILE_COLOR, ROW_COUNT, WALL_COLOR},
-    terrain::tile_modifier::{TerrainAction, TerrainGenerationEvent, TerrainNode},
+    entities::tile::{
+        EndUpdatedEvent, Tile, COL_COUNT, END_TILE_COLOR, MUD_COLOR, ROAD_COLOR, ROW_COUNT,
+        START_TILE_COLOR, WALL_COLOR, WATER_COLOR,
+    },
+    terrain::tile_modifier::{BuildType, TerrainAction, TerrainGenerationEvent, TerrainNode},
 };
 use std::{collections::VecDeque, time::Duration};
 
@@ -36,10 +39,10 @@ impl Plugin for TerrainTileAnimationPlugin {
 }
 
 #[derive(Resource)]
-struct TerrainAnimationGate {
+pub struct TerrainAnimationGate {
     timer: Timer,
     event_queues: Vec<EventQueueWithTimesFired>,
-    fast_mode_enabled: bool,
+    pub fast_mode_enabled: bool,
 }
 
 struct EventQueueWithTimesFired {
@@ -49,7 +52,18 @@ struct EventQueueWithTimesFired {
 
 struct AnimationFromTerrain {
     event: TerrainNode,
-    is_wall: bool,
+    color: Option<Color>,
+}
+
+fn build_type_color(build_type: BuildType) -> Color {
+    match build_type {
+        BuildType::Wall => WALL_COLOR,
+        BuildType::End => END_TILE_COLOR,
+        BuildType::Start => START_TILE_COLOR,
+        BuildType::Road => ROAD_COLOR,
+        BuildType::Mud => MUD_COLOR,
+        BuildType::Water => WATER_COLOR,
+    }
 }
 
 fn initiate_animation(
@@ -84,11 +98,7 @@ fn initiate_animation(
                     for (tile, mut anim, _mesh, _vis) in &mut q_tiles {
                         if tile.id == event.event.tile_id {
                             anim.update_color = true;
-                            anim.super_color = if event.is_wall {
-                                Some(WALL_COLOR)
-                            } else {
-                                None
-                            };
+                            anim.super_color = event.color;
                             if anim.state == TileAnimationState::Ran {
                                 anim.state = TileAnimationState::Initiated;
                             }
@@ -111,10 +121,14 @@ fn handle_terrain_event(
         for event in events.terrain_events.clone() {
             for tile in &q_tiles {
                 if event.tile_id == tile.id {
-                    let is_wall = event.action == TerrainAction::Added;
+                    let color = if event.action == TerrainAction::Added {
+                        Some(build_type_color(event.build_type))
+                    } else {
+                        None
+                    };
                     new_animation.push_front(AnimationFromTerrain {
                         event: event.clone(),
-                        is_wall,
+                        color,
                     });
                 }
             }
@@ -10,6 +10,28 @@ pub(crate) const GROUND_T_BORDER: f32 = GROUND_H / 2.;
 pub(crate) const GROUND_R_BORDER: f32 = GROUND_W / 2.;
 pub(crate) const GROUND_B_BORDER: f32 = -GROUND_H / 2.;
 
+// Shortest way from one point to another, which might be across the world wrap
+pub(crate) fn wrapped_offset(from: Vec2, to: Vec2) -> Vec2 {
+    let mut offset = to - from;
+    if offset.x > GROUND_W / 2. {
+        offset.x -= GROUND_W;
+    } else if offset.x < -GROUND_W / 2. {
+        offset.x += GROUND_W;
+    }
+    if offset.y > GROUND_H / 2. {
+        offset.y -= GROUND_H;
+    } else if offset.y < -GROUND_H / 2. {
+        offset.y += GROUND_H;
+    }
+    offset
+}
+
+// Whether the short way between two points goes across the world wrap, a line drawn between them
+// would cut back over the whole grid instead
+pub(crate) fn crosses_world_wrap(from: Vec2, to: Vec2) -> bool {
+    wrapped_offset(from, to) != to - from
+}
+
 pub struct GroundPlugin;
 
 impl Plugin for GroundPlugin {
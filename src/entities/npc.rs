@@ -0,0 +1,202 @@
+use bevy::prelude::*;
+use pathfinder::pathfinding::algorithms::cooperative::{agent_paths, find_conflicts, Conflict};
+
+use crate::input::{InputAction, KeyboardInputEvent};
+use crate::{
+    current_tile::emitter::CurrentMouseTileEvent,
+    entities::{
+        ground::crosses_world_wrap,
+        player::movement::{has_arrived, PlayerMovement},
+        tile::{tile_center, tile_under, Tile, TileGrid, TileType, TILE_OFFSET},
+    },
+    pathfinding::emit_pathfinding::{plans_out_of_date, AlgorithmInUse},
+};
+
+const NPC_SPEED: f32 = 200.;
+// Golden angle, neighbors in spawn order never end up with similar colors
+const NPC_HUE_STEP: f32 = 137.5;
+const CONFLICT_COLOR: Color = Color::srgb(1., 0., 0.);
+
+pub struct NpcPlugin;
+
+impl Plugin for NpcPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Npcs {
+            cooperative: true,
+            count: 0,
+            conflicts: vec![],
+            step: 0,
+            replan: false,
+        })
+        .add_systems(
+            Update,
+            (spawn_npcs_from_key_input, walk_npcs, draw_npc_plans),
+        )
+        .add_systems(
+            PostUpdate,
+            plan_npcs.run_if(plans_out_of_date.or(|npcs: Res<Npcs>| npcs.replan)),
+        );
+    }
+}
+
+/*
+ * NPCs walk to the End together, a tile each per step, nobody starting the next step until
+ * everyone has finished this one. Each plans with the current algorithm, and with cooperative on
+ * they take turns reserving tiles so they don't walk into each other (see `agent_paths`).
+ *
+ * Conflicts are wherever two of them would still end up on the same tile (or swap tiles) at the
+ * same step, the ones still ahead are circled.
+ */
+#[derive(Resource)]
+pub struct Npcs {
+    pub cooperative: bool,
+    pub count: usize,
+    pub conflicts: Vec<Conflict>,
+    // Which step of their paths everyone is walking to
+    step: usize,
+    replan: bool,
+}
+
+#[derive(Component)]
+struct Npc {
+    // Spawn order, NPCs that spawned earlier plan first
+    order: usize,
+    hue: f32,
+    // A tile per step, waits repeat the tile
+    path: Vec<(usize, usize)>,
+}
+
+// F11 puts an NPC on the tile under the mouse, Delete clears them all and F12 toggles cooperative
+#[allow(clippy::too_many_arguments)]
+fn spawn_npcs_from_key_input(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tiles: Query<&Tile>,
+    q_npcs: Query<(Entity, &Npc)>,
+    mut npcs: ResMut<Npcs>,
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut current_mouse_tile_reader: EventReader<CurrentMouseTileEvent>,
+    mut current_mouse_tile: Local<Option<usize>>,
+    mut spawned: Local<usize>,
+) {
+    for event in current_mouse_tile_reader.read() {
+        *current_mouse_tile = event.id;
+    }
+    for event in keyboard_input_reader.read() {
+        if event.action != InputAction::Pressed {
+            continue;
+        }
+        match event.key {
+            KeyCode::F11 => {
+                let Some(tile) = current_mouse_tile.and_then(|mouse_tile| {
+                    tiles
+                        .iter()
+                        .find(|tile| tile.id == mouse_tile && tile.tile_type != TileType::Wall)
+                }) else {
+                    continue;
+                };
+                let hue = (*spawned as f32 * NPC_HUE_STEP) % 360.;
+                let center = tile_center((tile.row, tile.col));
+                commands.spawn((
+                    Npc {
+                        order: *spawned,
+                        hue,
+                        path: vec![],
+                    },
+                    PlayerMovement::from_velocity_and_up_direction(
+                        (NPC_SPEED, NPC_SPEED),
+                        (-1., 0.),
+                    ),
+                    Mesh2d(meshes.add(CircularSector::new(12., 0.7))),
+                    MeshMaterial2d(materials.add(Color::hsl(hue, 0.80, 0.55))),
+                    Transform::from_xyz(center.x, center.y, 1.),
+                ));
+                *spawned += 1;
+                npcs.count += 1;
+                npcs.replan = true;
+            }
+            KeyCode::F12 => {
+                npcs.cooperative = !npcs.cooperative;
+                npcs.replan = true;
+            }
+            KeyCode::Delete => {
+                for (entity, _) in &q_npcs {
+                    commands.entity(entity).despawn();
+                }
+                npcs.count = 0;
+                npcs.conflicts.clear();
+            }
+            _ => {}
+        }
+    }
+}
+
+// Everyone plans again from whichever tile they're on, and starts over at the first step
+fn plan_npcs(
+    algo: Res<AlgorithmInUse>,
+    tiles: Query<&Tile>,
+    mut q_npcs: Query<(&Transform, &mut Npc)>,
+    mut npcs: ResMut<Npcs>,
+) {
+    npcs.replan = false;
+
+    let mut q_npcs: Vec<_> = q_npcs.iter_mut().collect();
+    q_npcs.sort_by_key(|(_, npc)| npc.order);
+    let starts: Vec<(usize, usize)> = q_npcs
+        .iter()
+        .map(|(xf, _)| tile_under(xf.translation.truncate()))
+        .collect();
+    let tiles: Vec<&Tile> = tiles.iter().collect();
+    let tile_grid = TileGrid::from_tiles(&tiles);
+    let paths = agent_paths(&tile_grid.grid, &starts, &algo, npcs.cooperative);
+
+    npcs.conflicts = find_conflicts(&tile_grid.grid, &paths);
+    npcs.step = 0;
+    for ((_, npc), path) in q_npcs.iter_mut().zip(paths) {
+        npc.path = path;
+    }
+}
+
+// Where an NPC should be at `step`, the end of its path once it's run out
+fn tile_at(npc: &Npc, step: usize) -> Option<(usize, usize)> {
+    npc.path.get(step).or(npc.path.last()).copied()
+}
+
+fn walk_npcs(mut q_npcs: Query<(&Transform, &Npc, &mut PlayerMovement)>, mut npcs: ResMut<Npcs>) {
+    let everyone_there = q_npcs.iter().all(|(xf, npc, _)| {
+        tile_at(npc, npcs.step)
+            .is_none_or(|pos| has_arrived(xf.translation.truncate(), tile_center(pos)))
+    });
+    let steps_left = q_npcs
+        .iter()
+        .any(|(_, npc, _)| npcs.step + 1 < npc.path.len());
+    if everyone_there && steps_left {
+        npcs.step += 1;
+    }
+
+    for (_, npc, mut movement) in &mut q_npcs {
+        movement.steer_to(tile_at(npc, npcs.step).map(tile_center));
+    }
+}
+
+fn draw_npc_plans(mut gizmos: Gizmos, q_npcs: Query<(&Transform, &Npc)>, npcs: Res<Npcs>) {
+    for (xf, npc) in &q_npcs {
+        let color = Color::hsl(npc.hue, 0.80, 0.55);
+        let mut from = xf.translation.truncate();
+        for &pos in npc.path.iter().skip(npcs.step) {
+            let to = tile_center(pos);
+            if !crosses_world_wrap(from, to) {
+                gizmos.line_2d(from, to, color);
+            }
+            from = to;
+        }
+    }
+    for conflict in npcs
+        .conflicts
+        .iter()
+        .filter(|conflict| conflict.time >= npcs.step)
+    {
+        gizmos.circle_2d(tile_center(conflict.pos), TILE_OFFSET, CONFLICT_COLOR);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use movement::PlayerMovement;
 
+pub mod click_to_move;
 pub mod movement;
 
 const PLAYER_COLOR: Color = Color::hsl(0., 1.0, 0.5);
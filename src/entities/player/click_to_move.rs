@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use super::{
+    movement::{has_arrived, PlayerMovement},
+    Player,
+};
+use crate::input::{InputAction, KeyboardInputEvent, MouseInputEvent};
+use crate::{
+    current_tile::emitter::CurrentMouseTileEvent,
+    entities::{
+        ground::crosses_world_wrap,
+        tile::{Tile, TileType},
+    },
+    pathfinding::emit_pathfinding::{plan_path, plans_out_of_date, AlgorithmInUse},
+};
+
+const ROUTE_COLOR: Color = Color::srgba(1., 1., 1., 0.4);
+
+pub struct ClickToMovePlugin;
+
+impl Plugin for ClickToMovePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClickToMove::default())
+            .add_systems(
+                Update,
+                (
+                    (
+                        set_click_to_move_from_key_input,
+                        set_goal_from_mouse_input,
+                        walk_route,
+                    )
+                        .chain(),
+                    draw_route,
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                plan_route.run_if(
+                    plans_out_of_date.or(|click_to_move: Res<ClickToMove>| click_to_move.replan),
+                ),
+            );
+    }
+}
+
+/*
+ * The player walks the path the current algorithm finds, to the nearest End or to a right-clicked
+ * tile, a tile center at a time. Walls changing, a different algorithm or the player being moved
+ * by hand plans it again from wherever they are.
+ */
+#[derive(Resource, Default)]
+pub struct ClickToMove {
+    pub enabled: bool,
+    // Right-clicked tile, None heads for the End
+    goal: Option<usize>,
+    // Tiles still to walk and where they are, the next one first
+    route: VecDeque<(usize, Vec2)>,
+    replan: bool,
+}
+
+fn set_click_to_move_from_key_input(
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut click_to_move: ResMut<ClickToMove>,
+) {
+    for event in keyboard_input_reader.read() {
+        if event.action == InputAction::Pressed && event.key == KeyCode::F7 {
+            click_to_move.enabled = !click_to_move.enabled;
+            click_to_move.goal = None;
+            click_to_move.route.clear();
+            click_to_move.replan = true;
+        }
+    }
+}
+
+// Right-click picks where to go instead of removing tiles while walking
+fn set_goal_from_mouse_input(
+    tiles: Query<&Tile>,
+    mut click_to_move: ResMut<ClickToMove>,
+    mut current_mouse_tile_reader: EventReader<CurrentMouseTileEvent>,
+    mut mouse_input_reader: EventReader<MouseInputEvent>,
+    mut current_mouse_tile: Local<Option<usize>>,
+) {
+    for event in current_mouse_tile_reader.read() {
+        *current_mouse_tile = event.id;
+    }
+    for event in mouse_input_reader.read() {
+        if !click_to_move.enabled
+            || event.key != MouseButton::Right
+            || event.action != InputAction::Pressed
+        {
+            continue;
+        }
+        let Some(goal) = *current_mouse_tile else {
+            continue;
+        };
+        if tiles
+            .iter()
+            .any(|tile| tile.id == goal && tile.tile_type != TileType::Wall)
+        {
+            click_to_move.goal = Some(goal);
+            click_to_move.replan = true;
+        }
+    }
+}
+
+fn walk_route(
+    mut player: Query<(&Transform, &mut PlayerMovement), With<Player>>,
+    mut click_to_move: ResMut<ClickToMove>,
+) {
+    let Ok((player_xf, mut movement)) = player.get_single_mut() else {
+        return;
+    };
+    if !click_to_move.enabled {
+        movement.steer_to(None);
+        return;
+    }
+    let player_position = player_xf.translation.truncate();
+
+    // Moved by hand, pick the route back up from wherever they let go
+    if movement.is_steered_by_keys() {
+        click_to_move.replan = true;
+        return;
+    }
+
+    while let Some(&(_, next)) = click_to_move.route.front() {
+        if !has_arrived(player_position, next) {
+            break;
+        }
+        click_to_move.route.pop_front();
+    }
+    movement.steer_to(click_to_move.route.front().map(|&(_, next)| next));
+}
+
+fn plan_route(
+    algo: Res<AlgorithmInUse>,
+    tiles: Query<(&Tile, &Transform), Without<Player>>,
+    player: Query<&Transform, With<Player>>,
+    mut click_to_move: ResMut<ClickToMove>,
+) {
+    if !click_to_move.enabled {
+        return;
+    }
+    let Ok(player_xf) = player.get_single() else {
+        return;
+    };
+    click_to_move.replan = false;
+    let player_position = player_xf.translation.truncate();
+
+    // Whichever tile the player is over right now, the current tile event can be a frame behind
+    let Some((current_tile, _)) = tiles.iter().min_by(|(_, a), (_, b)| {
+        let a = a.translation.truncate().distance_squared(player_position);
+        let b = b.translation.truncate().distance_squared(player_position);
+        a.total_cmp(&b)
+    }) else {
+        return;
+    };
+    let tile_refs: Vec<&Tile> = tiles.iter().map(|(tile, _)| tile).collect();
+    let path = plan_path(&algo, &tile_refs, current_tile.id, click_to_move.goal);
+    click_to_move.route = path
+        .into_iter()
+        .skip(1)
+        .filter_map(|tile_id| {
+            tiles
+                .iter()
+                .find(|(tile, _)| tile.id == tile_id)
+                .map(|(_, xf)| (tile_id, xf.translation.truncate()))
+        })
+        .collect();
+}
+
+fn draw_route(
+    mut gizmos: Gizmos,
+    click_to_move: Res<ClickToMove>,
+    player: Query<&Transform, With<Player>>,
+) {
+    if !click_to_move.enabled {
+        return;
+    }
+    let Ok(player_xf) = player.get_single() else {
+        return;
+    };
+    let mut from = player_xf.translation.truncate();
+    for &(_, to) in &click_to_move.route {
+        if !crosses_world_wrap(from, to) {
+            gizmos.line_2d(from, to, ROUTE_COLOR);
+        }
+        from = to;
+    }
+}
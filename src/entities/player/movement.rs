@@ -1,13 +1,19 @@
 use bevy::prelude::*;
 
+use super::Player;
 use crate::input::{InputAction, KeyboardInputEvent};
 use crate::{
     collision::collidable::CollidedEvent,
     entities::ground::{
-        GROUND_B_BORDER, GROUND_H, GROUND_L_BORDER, GROUND_R_BORDER, GROUND_T_BORDER, GROUND_W,
+        wrapped_offset, GROUND_B_BORDER, GROUND_H, GROUND_L_BORDER, GROUND_R_BORDER,
+        GROUND_T_BORDER, GROUND_W,
     },
 };
 
+// Close enough to a steering target to call it reached and head for the next one
+const ARRIVE_DISTANCE: f32 = 2.;
+
+// Anything that walks like the player, keys and collisions only ever move the player itself
 #[derive(Component)]
 pub(crate) struct PlayerMovement {
     curr: TransformState,
@@ -15,6 +21,8 @@ pub(crate) struct PlayerMovement {
     direction: Direction,
     velocity: Vec2,
     up_dir: Vec2,
+    // Where to walk when no movement keys are held, set by whatever is following a path
+    steer_to: Option<Vec2>,
 }
 
 #[derive(Default)]
@@ -37,8 +45,24 @@ impl PlayerMovement {
             direction: Direction::default(),
             velocity: Vec2::new(velocity.0, velocity.1),
             up_dir: Vec2::new(up_direction.0, up_direction.1),
+            steer_to: None,
         }
     }
+
+    // Walks the short way there, across the world wrap the target is just past the edge rather
+    // than back across the grid
+    pub fn steer_to(&mut self, target: Option<Vec2>) {
+        self.steer_to = target;
+    }
+
+    pub fn is_steered_by_keys(&self) -> bool {
+        self.direction.vector != Vec2::ZERO
+    }
+}
+
+// Whether something at `position` has made it to a `steer_to` target
+pub(crate) fn has_arrived(position: Vec2, target: Vec2) -> bool {
+    wrapped_offset(position, target).length() <= ARRIVE_DISTANCE
 }
 
 #[derive(Clone)]
@@ -56,25 +80,35 @@ impl Default for TransformState {
     }
 }
 
+// Puts the player down somewhere new without sliding there, like when a map is loaded
+#[derive(Event)]
+pub(crate) struct PlayerTeleportEvent {
+    pub x: f32,
+    pub y: f32,
+}
+
 pub struct PlayerMovementPlugin;
 
 impl Plugin for PlayerMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(FixedUpdate, player_movement).add_systems(
-            Update,
-            (
-                teleport_player_at_bounds,
-                rebound_player,
-                set_player_direction_from_input,
-                transform_movement_interpolate,
-            ),
-        );
+        app.add_event::<PlayerTeleportEvent>()
+            .add_systems(FixedUpdate, player_movement)
+            .add_systems(
+                Update,
+                (
+                    teleport_player_at_bounds,
+                    teleport_player_from_event,
+                    rebound_player,
+                    set_player_direction_from_input,
+                    transform_movement_interpolate,
+                ),
+            );
     }
 }
 
 fn set_player_direction_from_input(
     mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
-    mut movement: Query<&mut PlayerMovement>,
+    mut movement: Query<&mut PlayerMovement, With<Player>>,
 ) {
     for event in keyboard_input_reader.read() {
         for mut m in &mut movement {
@@ -137,7 +171,7 @@ fn set_player_direction_from_input(
 
 fn rebound_player(
     mut collided_event_reader: EventReader<CollidedEvent>,
-    mut movement: Query<&mut PlayerMovement>,
+    mut movement: Query<&mut PlayerMovement, With<Player>>,
 ) {
     for _ in collided_event_reader.read() {
         for mut p_mv in &mut movement {
@@ -179,6 +213,20 @@ fn teleport_player_at_bounds(mut movement: Query<(&mut Transform, &mut PlayerMov
     }
 }
 
+fn teleport_player_from_event(
+    mut teleport_reader: EventReader<PlayerTeleportEvent>,
+    mut movement: Query<(&mut Transform, &mut PlayerMovement), With<Player>>,
+) {
+    for event in teleport_reader.read() {
+        for (mut xf, mut state) in &mut movement {
+            xf.translation.x = event.x;
+            xf.translation.y = event.y;
+            state.curr.position = Some(xf.translation);
+            state.prev.position = Some(xf.translation);
+        }
+    }
+}
+
 fn player_movement(time: Res<Time>, mut movement: Query<(&Transform, &mut PlayerMovement)>) {
     for (xf, mut m) in &mut movement {
         if m.curr.position.is_none() {
@@ -190,19 +238,35 @@ fn player_movement(time: Res<Time>, mut movement: Query<(&Transform, &mut Player
             direction,
             velocity,
             up_dir,
+            steer_to,
         } = &mut *m;
         *prev = curr.clone();
 
+        // Keys win, steering only happens when nothing's held
+        let mut heading = direction.vector;
         if let Some(curr_position) = &mut curr.position {
             if direction.vector != Vec2::ZERO {
                 curr_position.x += direction.vector.x * velocity.x * time.delta_secs();
                 curr_position.y += direction.vector.y * velocity.y * time.delta_secs();
+            } else if let Some(target) = steer_to {
+                let to_target = wrapped_offset(curr_position.truncate(), *target);
+                let target = curr_position.truncate() + to_target;
+                let step = velocity.x * time.delta_secs();
+                heading = to_target.normalize_or_zero();
+                // Land right on it instead of wobbling around it
+                if to_target.length() <= step {
+                    curr_position.x = target.x;
+                    curr_position.y = target.y;
+                } else {
+                    curr_position.x += heading.x * step;
+                    curr_position.y += heading.y * step;
+                }
             }
         }
 
         if let Some(curr_rotation) = &mut curr.rotation {
-            let angle = up_dir.y.atan2(up_dir.x) - direction.vector.x.atan2(direction.vector.y);
-            if direction.vector != Vec2::ZERO {
+            let angle = up_dir.y.atan2(up_dir.x) - heading.x.atan2(heading.y);
+            if heading != Vec2::ZERO {
                 *curr_rotation = Quat::from_rotation_z(angle);
             };
         } else {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use pathfinder::Grid;
 
 use super::ground::{GROUND_H, GROUND_W};
 use crate::{
@@ -13,6 +14,10 @@ pub const TEMP_TILE_COLOR_1: Color = Color::hsl(117., 0.67, 0.58);
 pub const TEMP_TILE_COLOR_2: Color = Color::hsla(171., 0.35, 0.68, 0.50);
 pub const END_TILE_COLOR: Color = Color::hsl(360., 0.80, 0.50);
 pub const WALL_COLOR: Color = Color::hsl(0., 0.71, 0.19);
+pub const ROAD_COLOR: Color = Color::hsl(40., 0.12, 0.62);
+pub const MUD_COLOR: Color = Color::hsl(28., 0.55, 0.30);
+pub const WATER_COLOR: Color = Color::hsl(210., 0.75, 0.45);
+pub const START_TILE_COLOR: Color = Color::hsl(275., 0.70, 0.55);
 
 pub const TILE_SIZE: f32 = 50.;
 pub const TILE_OFFSET: f32 = TILE_SIZE / 2.;
@@ -29,12 +34,7 @@ impl Plugin for TilePlugin {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum TileType {
-    Open,
-    End,
-    Wall,
-}
+pub use pathfinder::TileType;
 
 #[derive(Event)]
 pub struct EndUpdatedEvent {
@@ -61,6 +61,47 @@ impl Default for Tile {
     }
 }
 
+// The tiles as the algorithms see them, plus the way back from a position to a tile id
+pub struct TileGrid {
+    pub grid: Grid,
+    ids: Vec<Vec<usize>>,
+}
+
+impl TileGrid {
+    pub fn from_tiles(tiles: &[&Tile]) -> TileGrid {
+        let mut grid = Grid::new(ROW_COUNT, COL_COUNT);
+        let mut ids = vec![vec![0; COL_COUNT]; ROW_COUNT];
+        for tile in tiles {
+            grid.set_tile_type((tile.row, tile.col), tile.tile_type);
+            ids[tile.row][tile.col] = tile.id;
+        }
+        TileGrid { grid, ids }
+    }
+
+    pub fn id(&self, (row, col): (usize, usize)) -> usize {
+        self.ids[row][col]
+    }
+
+    pub fn pos(&self, id: usize) -> Option<(usize, usize)> {
+        self.grid.positions().find(|&pos| self.id(pos) == id)
+    }
+}
+
+// Middle of the tile at (row, col) in world space
+pub fn tile_center((row, col): (usize, usize)) -> Vec2 {
+    Vec2::new(
+        GROUND_L_BORDER + TILE_SIZE * col as f32 + TILE_OFFSET,
+        GROUND_T_BORDER - TILE_SIZE * row as f32 - TILE_OFFSET,
+    )
+}
+
+// (row, col) of the tile a world position is over
+pub fn tile_under(position: Vec2) -> (usize, usize) {
+    let row = ((GROUND_T_BORDER - position.y) / TILE_SIZE) as usize;
+    let col = ((position.x - GROUND_L_BORDER) / TILE_SIZE) as usize;
+    (row.min(ROW_COUNT - 1), col.min(COL_COUNT - 1))
+}
+
 static COUNTER: AtomicUsize = AtomicUsize::new(1);
 fn get_tile_id() -> usize {
     COUNTER.fetch_add(1, Ordering::SeqCst)
@@ -99,7 +140,7 @@ fn spawn_tile_grid(
                 Tile {
                     row: r,
                     col: c,
-                    tile_type: tile_type.clone(),
+                    tile_type,
                     ..Default::default()
                 },
                 TileAnimation {
@@ -141,6 +182,26 @@ fn handle_terrain_event(
                             tile.tile_type = TileType::Wall;
                             commands.entity(entity_id).insert(Collidable);
                         }
+                        if event.build_type.is_weighted() {
+                            if tile.tile_type == TileType::End {
+                                end_updated_writer.send(EndUpdatedEvent {
+                                    new_end_id: None,
+                                    old_end_id: Some(tile.id),
+                                });
+                            }
+                            tile.tile_type = event.build_type.into();
+                            commands.entity(entity_id).remove::<Collidable>();
+                        }
+                        if event.build_type == BuildType::Start {
+                            if tile.tile_type == TileType::End {
+                                end_updated_writer.send(EndUpdatedEvent {
+                                    new_end_id: None,
+                                    old_end_id: Some(tile.id),
+                                });
+                            }
+                            tile.tile_type = TileType::Start;
+                            commands.entity(entity_id).remove::<Collidable>();
+                        }
                         if event.build_type == BuildType::End {
                             tile.tile_type = TileType::End;
                             commands.entity(entity_id).remove::<Collidable>();
@@ -149,13 +210,6 @@ fn handle_terrain_event(
                                 old_end_id: None,
                             });
                         }
-                    } else if event.build_type == BuildType::End && tile.tile_type == TileType::End
-                    {
-                        tile.tile_type = TileType::Open;
-                        end_updated_writer.send(EndUpdatedEvent {
-                            new_end_id: None,
-                            old_end_id: Some(tile.id),
-                        });
                     }
                 } else if event.action == TerrainAction::Removed {
                     if is_current_tile {
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation::tile::{pathfinding::Playback, terrain::TerrainAnimationGate},
+    collision::collidable::CollideStatus,
+    entities::{npc::Npcs, player::click_to_move::ClickToMove},
+    pathfinding::{
+        compare::Comparison,
+        emit_pathfinding::{AlgorithmInUse, Precalc, SearchStats},
+        flow_field::FlowField,
+    },
+    terrain::tile_modifier::{
+        BuildType, MapFileStatus, TerrainAlgorithmInUse, TerrainSettingsInUse,
+    },
+};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (spawn_hud_text, spawn_comparison_table))
+            .add_systems(Update, (update_hud_text, update_comparison_table));
+    }
+}
+
+#[derive(Component)]
+struct HudText;
+
+#[derive(Component)]
+struct ComparisonTitle;
+
+// One line per compared algorithm, the index into the comparison's runs
+#[derive(Component)]
+struct ComparisonRow(usize);
+
+// Can't compare more algorithms than there are
+const COMPARISON_ROWS: usize = 6;
+
+fn spawn_hud_text(mut commands: Commands) {
+    commands.spawn((
+        HudText,
+        Text::default(),
+        TextFont {
+            font_size: 20.,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.),
+            left: Val::Px(12.),
+            ..default()
+        },
+    ));
+}
+
+fn path_text(stats: &SearchStats) -> String {
+    if stats.path_len == 0 {
+        "no path".to_string()
+    } else {
+        format!("{} tiles, cost {}", stats.path_len, stats.path_cost)
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+// Everything the keys change, so nobody has to remember what they pressed
+#[allow(clippy::too_many_arguments)]
+fn update_hud_text(
+    algo: Res<AlgorithmInUse>,
+    precalc: Res<Precalc>,
+    terrain_algo: Res<TerrainAlgorithmInUse>,
+    terrain_settings: Res<TerrainSettingsInUse>,
+    terrain_animation_gate: Res<TerrainAnimationGate>,
+    build_type: Res<BuildType>,
+    collide_status: Res<CollideStatus>,
+    click_to_move: Res<ClickToMove>,
+    playback: Res<Playback>,
+    flow: Res<FlowField>,
+    npcs: Res<Npcs>,
+    map_file: Res<MapFileStatus>,
+    mut q_text: Query<&mut Text, With<HudText>>,
+) {
+    let stats = &precalc.stats;
+    let path = path_text(stats);
+    let playback = if playback.paused {
+        "paused".to_string()
+    } else {
+        format!(
+            "{} steps every {}ms",
+            playback.steps_per_tick, playback.delay_ms
+        )
+    };
+    let flow = if flow.enabled {
+        format!("{} agents, took {:.2?}", flow.swarm_size, flow.compute_time)
+    } else {
+        "off".to_string()
+    };
+
+    let text = format!(
+        "Algorithm: {:?}\n\
+         Direction offset: {}, random: {}\n\
+         World wrap: {}, bidirectional: {}\n\
+         All ends: {}, from Start tile: {}\n\
+         Maze: {:?}, fast: {}\n\
+         Caves: fill {:.2}, passes {}, braid {:.0}%\n\
+         Building: {:?}\n\
+         Map file: {}\n\
+         Collision: {}, walking: {}\n\
+         Playback: {}\n\
+         Flow field: {}\n\
+         NPCs: {}, cooperative: {}, conflicts: {}\n\
+         \n\
+         Visited: {}\n\
+         Path: {}\n\
+         Took: {:.2?}",
+        algo.algorithm,
+        algo.direction_offset,
+        on_off(algo.random_direction),
+        on_off(algo.world_wrap_enabled),
+        on_off(algo.bidirectional),
+        on_off(algo.visit_all_goals),
+        on_off(algo.use_start_tile),
+        **terrain_algo,
+        on_off(terrain_animation_gate.fast_mode_enabled),
+        terrain_settings.cave_fill_ratio,
+        terrain_settings.cave_iterations,
+        terrain_settings.braid_ratio * 100.,
+        *build_type,
+        **map_file,
+        on_off(*collide_status == CollideStatus::Enabled),
+        on_off(click_to_move.enabled),
+        playback,
+        flow,
+        npcs.count,
+        on_off(npcs.cooperative),
+        npcs.conflicts.len(),
+        stats.visited,
+        path,
+        stats.compute_time,
+    );
+    for mut hud_text in &mut q_text {
+        if hud_text.0 != text {
+            hud_text.0 = text.clone();
+        }
+    }
+}
+
+// Top right, each line in the color its algorithm is painting with
+fn spawn_comparison_table(mut commands: Commands) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.),
+            right: Val::Px(12.),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        })
+        .with_children(|table| {
+            table.spawn((
+                ComparisonTitle,
+                Text::default(),
+                TextFont {
+                    font_size: 20.,
+                    ..default()
+                },
+            ));
+            for row in 0..COMPARISON_ROWS {
+                table.spawn((
+                    ComparisonRow(row),
+                    Text::default(),
+                    TextFont {
+                        font_size: 20.,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+fn update_comparison_table(
+    comparison: Res<Comparison>,
+    mut q_title: Query<&mut Text, (With<ComparisonTitle>, Without<ComparisonRow>)>,
+    mut q_rows: Query<(&ComparisonRow, &mut Text, &mut TextColor)>,
+) {
+    if !comparison.is_changed() {
+        return;
+    }
+
+    let title = if !comparison.enabled {
+        String::new()
+    } else if comparison.runs.is_empty() {
+        let compared: Vec<String> = comparison
+            .algorithms
+            .iter()
+            .map(|algorithm| format!("{:?}", algorithm))
+            .collect();
+        format!("Comparing {} (J to run)", compared.join(", "))
+    } else {
+        "Comparing".to_string()
+    };
+    for mut title_text in &mut q_title {
+        title_text.0 = title.clone();
+    }
+
+    for (ComparisonRow(row), mut row_text, mut row_color) in &mut q_rows {
+        match comparison.runs.get(*row).filter(|_| comparison.enabled) {
+            Some(run) => {
+                row_text.0 = format!(
+                    "{:?}: visited {}, path {}, took {:.2?}",
+                    run.algorithm,
+                    run.stats.visited,
+                    path_text(&run.stats),
+                    run.stats.compute_time
+                );
+                row_color.0 = Color::hsl(run.hue as f32, 0.30, 0.73);
+            }
+            None => row_text.0 = String::new(),
+        }
+    }
+}
@@ -6,6 +6,7 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<KeyboardInputEvent>()
             .add_event::<MouseInputEvent>()
+            .insert_resource(KeyboardCaptured(false))
             .add_systems(Update, broadcast_input);
     }
 }
@@ -16,6 +17,15 @@ pub enum InputAction {
     Released,
 }
 
+// Something is typing (a seed), key presses aren't broadcast until it lets go
+#[derive(Resource)]
+pub struct KeyboardCaptured(pub bool);
+
+// Shift and a number picks a maze algorithm, a number alone picks a pathfinding one
+pub fn shift_held(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+}
+
 #[derive(Event)]
 pub struct KeyboardInputEvent {
     pub key: KeyCode,
@@ -30,6 +40,7 @@ pub struct MouseInputEvent {
 
 fn broadcast_input(
     keys: Res<ButtonInput<KeyCode>>,
+    captured: Res<KeyboardCaptured>,
     mouse: Res<ButtonInput<MouseButton>>,
     mut player_input_writer: EventWriter<KeyboardInputEvent>,
     mut player_mouse_input_writer: EventWriter<MouseInputEvent>,
@@ -47,7 +58,8 @@ fn broadcast_input(
             action: InputAction::Released,
         });
     }
-    for key in keys.get_just_pressed() {
+    // Releases still go out so nothing gets stuck held down
+    for key in keys.get_just_pressed().filter(|_| !captured.0) {
         player_input_writer.send(KeyboardInputEvent {
             key: *key,
             action: InputAction::Pressed,
@@ -6,11 +6,17 @@ use collision::collidable::CollidablePlugin;
 use current_tile::emitter::EmitCurrentTilePlugin;
 use entities::camera::SceneCameraPlugin;
 use entities::ground::GroundPlugin;
+use entities::npc::NpcPlugin;
+use entities::player::click_to_move::ClickToMovePlugin;
 use entities::player::movement::PlayerMovementPlugin;
 use entities::player::PlayerPlugin;
 use entities::tile::TilePlugin;
+use hud::HudPlugin;
 use input::InputPlugin;
+use pathfinding::compare::ComparePlugin;
 use pathfinding::emit_pathfinding::EmitPathfindingPlugin;
+use pathfinding::flow_field::FlowFieldPlugin;
+use seed::SeedPlugin;
 use terrain::tile_modifier::TileModifierPlugin;
 
 mod animation {
@@ -27,16 +33,19 @@ mod debug;
 mod entities {
     pub mod camera;
     pub mod ground;
+    pub mod npc;
     pub mod player;
     pub mod tile;
 }
+mod hud;
 mod input;
 mod pathfinding {
-    pub mod algorithms;
+    pub mod compare;
     pub mod emit_pathfinding;
+    pub mod flow_field;
 }
+mod seed;
 mod terrain {
-    pub mod algorithms;
     pub mod tile_modifier;
 }
 
@@ -54,12 +63,18 @@ fn main() {
         .add_plugins(DefaultPlugins.set(window_plugin))
         .add_plugins((PlayerPlugin, GroundPlugin, TilePlugin, SceneCameraPlugin))
         .add_plugins((
+            ClickToMovePlugin,
             CollidablePlugin,
+            ComparePlugin,
             EmitCurrentTilePlugin,
             EmitPathfindingPlugin,
+            FlowFieldPlugin,
             HighlightCursorTilePlugin,
+            HudPlugin,
             InputPlugin,
+            NpcPlugin,
             PlayerMovementPlugin,
+            SeedPlugin,
             TileAnimationPlugin,
             TileModifierPlugin,
         ))
@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+use pathfinder::{Algorithm, Options};
+
+use crate::input::{InputAction, KeyboardInputEvent};
+use crate::{
+    entities::tile::Tile,
+    pathfinding::emit_pathfinding::{
+        run_algo, AlgorithmInUse, PathEvent, PathfindingEvent, Precalc, SearchStats,
+    },
+};
+
+// Same order as the number keys, F1 toggles the first one in or out and so on
+const COMPARABLE: [Algorithm; 6] = [
+    Algorithm::Dijkstra,
+    Algorithm::AStar,
+    Algorithm::AggressiveStar,
+    Algorithm::DFS,
+    Algorithm::BFS,
+    Algorithm::JumpPointSearch,
+];
+const COMPARE_KEYS: [KeyCode; 6] = [
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+];
+// Compared searches get hues spread evenly around the wheel starting here
+const COMPARE_BASE_HUE: usize = 200;
+
+pub struct ComparePlugin;
+
+impl Plugin for ComparePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Comparison {
+            enabled: false,
+            algorithms: vec![Algorithm::Dijkstra, Algorithm::AStar],
+            runs: vec![],
+        })
+        .add_systems(
+            Update,
+            (set_comparison_from_key_input, emit_comparison_by_button),
+        );
+    }
+}
+
+/*
+ * Runs every algorithm in `algorithms` from the same tile on the same grid, each with the rest of
+ * AlgorithmInUse's options. They're all sent at once so they animate (and step) together, each in
+ * its own hue.
+ */
+#[derive(Resource)]
+pub struct Comparison {
+    pub enabled: bool,
+    pub algorithms: Vec<Algorithm>,
+    // From the last time J or H was pressed
+    pub runs: Vec<ComparisonRun>,
+}
+
+pub struct ComparisonRun {
+    pub algorithm: Algorithm,
+    pub hue: usize,
+    pub stats: SearchStats,
+}
+
+fn set_comparison_from_key_input(
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut comparison: ResMut<Comparison>,
+) {
+    for event in keyboard_input_reader.read() {
+        if event.action != InputAction::Pressed {
+            continue;
+        }
+        if event.key == KeyCode::Tab {
+            comparison.enabled = !comparison.enabled;
+            continue;
+        }
+        let Some(index) = COMPARE_KEYS.iter().position(|&key| key == event.key) else {
+            continue;
+        };
+        let algorithm = COMPARABLE[index];
+        if comparison.algorithms.contains(&algorithm) {
+            comparison
+                .algorithms
+                .retain(|&compared| compared != algorithm);
+        } else {
+            comparison.algorithms.push(algorithm);
+            comparison.algorithms.sort_by_key(|&compared| {
+                COMPARABLE
+                    .iter()
+                    .position(|&comparable| comparable == compared)
+            });
+        }
+    }
+}
+
+fn emit_comparison_by_button(
+    algo: Res<AlgorithmInUse>,
+    precalc: Res<Precalc>,
+    tiles: Query<&Tile>,
+    mut comparison: ResMut<Comparison>,
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut pathfinding_writer: EventWriter<PathfindingEvent>,
+    mut path_writer: EventWriter<PathEvent>,
+) {
+    for input in keyboard_input_reader.read() {
+        if !comparison.enabled || input.action != InputAction::Pressed {
+            continue;
+        }
+        if input.key != KeyCode::KeyJ && input.key != KeyCode::KeyH {
+            continue;
+        }
+
+        // Rerun every time, the player has probably moved since the last one
+        let tiles: Vec<&Tile> = tiles.iter().collect();
+        let count = comparison.algorithms.len();
+        let mut runs = vec![];
+        for (i, &algorithm) in comparison.algorithms.iter().enumerate() {
+            let options = Options {
+                algorithm,
+                ..algo.0.clone()
+            };
+            let (visited, path, stats) = run_algo(&options, &tiles, precalc.current_tile());
+            let hue = COMPARE_BASE_HUE + i * 360 / count;
+            if input.key == KeyCode::KeyJ {
+                pathfinding_writer.send(PathfindingEvent {
+                    visited,
+                    hue: Some(hue),
+                });
+            } else {
+                path_writer.send(PathEvent {
+                    nodes: path,
+                    hue: Some(hue),
+                });
+            }
+            runs.push(ComparisonRun {
+                algorithm,
+                hue,
+                stats,
+            });
+        }
+        comparison.runs = runs;
+    }
+}
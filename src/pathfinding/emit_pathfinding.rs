@@ -1,13 +1,21 @@
 use bevy::prelude::*;
+use pathfinder::{
+    path_cost, pathfind,
+    pathfinding::algorithms::{dstar_lite::DStarLite, search_start},
+    Algorithm, Options, SearchResult,
+};
 
 use crate::current_tile::emitter::CurrentTileEvent;
-use crate::input::{InputAction, KeyboardInputEvent};
-use crate::{entities::tile::Tile, terrain::tile_modifier::TerrainGenerationEvent};
-
-use super::algorithms::{
-    astar::setup_and_run_astar, bfs::setup_and_run_bfs, dfs::setup_and_run_dfs,
-    dijkstra::setup_and_run_dijkstra, Algorithm,
+use crate::input::{shift_held, InputAction, KeyboardInputEvent};
+use crate::{
+    entities::tile::{Tile, TileGrid, TileType},
+    pathfinding::compare::Comparison,
+    seed::Seed,
+    terrain::tile_modifier::TerrainGenerationEvent,
 };
+use std::time::{Duration, Instant};
+
+pub use pathfinder::pathfinding::algorithms::SearchSide;
 
 pub struct EmitPathfindingPlugin;
 
@@ -15,17 +23,14 @@ impl Plugin for EmitPathfindingPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PathfindingEvent>()
             .add_event::<PathEvent>()
-            .insert_resource(AlgorithmInUse {
-                name: Algorithm::Dijkstra,
-                direction_offset: 0,
-                random_direction: false,
-                world_wrap_enabled: true,
-            })
+            .insert_resource(AlgorithmInUse(Options::default()))
             .insert_resource(Precalc {
                 visited: vec![],
                 path: vec![],
                 current_tile: 0,
+                stats: SearchStats::default(),
             })
+            .insert_resource(Replanner(None))
             .add_systems(
                 FixedUpdate,
                 (
@@ -36,7 +41,11 @@ impl Plugin for EmitPathfindingPlugin {
             )
             .add_systems(
                 Update,
-                (emit_pathfinding_by_button, set_algorithm_from_key_input),
+                (
+                    emit_pathfinding_by_button,
+                    set_algorithm_from_key_input,
+                    use_seed_for_random_direction,
+                ),
             );
     }
 }
@@ -44,57 +53,207 @@ impl Plugin for EmitPathfindingPlugin {
 #[derive(Event, Clone)]
 pub struct PathfindingEvent {
     pub visited: Vec<PathfindingNode>,
+    // Paint in this hue instead of the next one in the cycle, comparisons keep theirs apart
+    pub hue: Option<usize>,
 }
 
 #[derive(Event, Clone)]
 pub struct PathEvent {
     pub nodes: Vec<PathfindingNode>,
+    pub hue: Option<usize>,
 }
 
 #[derive(Clone, Debug)]
 pub struct PathfindingNode {
     pub tile_id: usize,
+    pub side: SearchSide,
+    // Cost from the start (or the end, for the end side of a bidirectional search)
+    pub g_score: usize,
+    // Parent tile in the search tree, None for path nodes and wherever the search started
+    pub previous_tile_id: Option<usize>,
+    // Tiles put on the frontier while this one was expanded, empty for path nodes
+    pub opened: Vec<usize>,
 }
 
-#[derive(Resource)]
-pub struct AlgorithmInUse {
-    pub name: Algorithm,
-    pub direction_offset: usize,
-    pub random_direction: bool,
-    pub world_wrap_enabled: bool,
-}
+#[derive(Resource, Deref, DerefMut)]
+pub struct AlgorithmInUse(pub Options);
 
 #[derive(Resource)]
 pub struct Precalc {
     visited: Vec<PathfindingNode>,
     path: Vec<PathfindingNode>,
     current_tile: usize,
+    pub stats: SearchStats,
+}
+
+impl Precalc {
+    // The tile searches start from, wherever the player was last
+    pub fn current_tile(&self) -> usize {
+        self.current_tile
+    }
 }
 
-fn run_algo(
-    algo: &AlgorithmInUse,
+// The last D* Lite search, kept so wall edits and moving only redo the part that changed
+#[derive(Resource)]
+struct Replanner(Option<DStarLite>);
+
+// How the last search went, for the HUD
+#[derive(Clone, Default)]
+pub struct SearchStats {
+    pub visited: usize,
+    // Tiles in the path, 0 if it never got there
+    pub path_len: usize,
+    pub path_cost: usize,
+    pub compute_time: Duration,
+}
+
+pub(crate) fn run_algo(
+    algo: &Options,
     tiles: &[&Tile],
     current_tile_id: usize,
-) -> (Vec<PathfindingNode>, Vec<PathfindingNode>) {
-    match algo.name {
-        Algorithm::AStar => setup_and_run_astar(&tiles, current_tile_id, false, algo),
-        Algorithm::AggressiveStar => setup_and_run_astar(&tiles, current_tile_id, true, algo),
-        Algorithm::BFS => setup_and_run_bfs(&tiles, current_tile_id, algo),
-        Algorithm::DFS => setup_and_run_dfs(&tiles, current_tile_id, algo),
-        Algorithm::Dijkstra => setup_and_run_dijkstra(&tiles, current_tile_id, algo),
+) -> (Vec<PathfindingNode>, Vec<PathfindingNode>, SearchStats) {
+    let tile_grid = TileGrid::from_tiles(tiles);
+    let current_tile_pos = tile_grid.pos(current_tile_id).unwrap_or((0, 0));
+    let started = Instant::now();
+    let result = pathfind(&tile_grid.grid, current_tile_pos, algo);
+    to_pathfinding_nodes(&tile_grid, result, started.elapsed())
+}
+
+fn to_pathfinding_nodes(
+    tile_grid: &TileGrid,
+    result: SearchResult,
+    compute_time: Duration,
+) -> (Vec<PathfindingNode>, Vec<PathfindingNode>, SearchStats) {
+    let stats = SearchStats {
+        visited: result.visited.len(),
+        path_len: result.path.len(),
+        path_cost: path_cost(&tile_grid.grid, &result.path),
+        compute_time,
+    };
+
+    let visited = result
+        .visited
+        .into_iter()
+        .map(|node| PathfindingNode {
+            tile_id: tile_grid.id(node.pos),
+            side: node.side,
+            g_score: node.g_score,
+            previous_tile_id: node.previous_node.map(|pos| tile_grid.id(pos)),
+            opened: node.opened.iter().map(|&pos| tile_grid.id(pos)).collect(),
+        })
+        .collect();
+    let mut g_score = 0;
+    let path = result
+        .path
+        .iter()
+        .enumerate()
+        .map(|(step, &pos)| {
+            if step > 0 {
+                g_score += path_cost(&tile_grid.grid, &result.path[step - 1..=step]);
+            }
+            PathfindingNode {
+                tile_id: tile_grid.id(pos),
+                side: SearchSide::Start,
+                g_score,
+                previous_tile_id: None,
+                opened: vec![],
+            }
+        })
+        .collect();
+    (visited, path, stats)
+}
+
+/*
+ * Everything but D* Lite searches from scratch. D* Lite picks its last search back up, so what J
+ * paints is only what a wall edit or the player moving made it expand again. A replan with nothing
+ * to expand (a second event in the same tick, a step along the path) keeps the last one's tiles.
+ */
+fn recalc(algo: &Options, tiles: &[&Tile], precalc: &mut Precalc, replanner: &mut Replanner) {
+    let tile_grid = TileGrid::from_tiles(tiles);
+    // Tours run a fresh search per leg
+    let touring = algo.visit_all_goals && tile_grid.grid.ends().len() > 1;
+    if algo.algorithm != Algorithm::DStarLite || touring {
+        replanner.0 = None;
+        let (visited, path, stats) = run_algo(algo, tiles, precalc.current_tile);
+        precalc.visited = visited;
+        precalc.path = path;
+        precalc.stats = stats;
+        return;
+    }
+
+    let current_tile_pos = tile_grid.pos(precalc.current_tile).unwrap_or((0, 0));
+    let start = search_start(&tile_grid.grid, current_tile_pos, algo);
+    let started = Instant::now();
+    let dstar = match &mut replanner.0 {
+        Some(dstar) if dstar.options() == algo => {
+            dstar.update_grid(&tile_grid.grid);
+            dstar.move_start(start);
+            dstar
+        }
+        _ => replanner
+            .0
+            .insert(DStarLite::new(&tile_grid.grid, start, algo)),
+    };
+    let result = dstar.search();
+    let (visited, path, stats) = to_pathfinding_nodes(&tile_grid, result, started.elapsed());
+
+    precalc.path = path;
+    precalc.stats.path_len = stats.path_len;
+    precalc.stats.path_cost = stats.path_cost;
+    if !visited.is_empty() {
+        precalc.visited = visited;
+        precalc.stats.visited = stats.visited;
+        precalc.stats.compute_time = stats.compute_time;
+    }
+}
+
+// Run condition for anything that plans over the tiles: walls changed or the algorithm did, so
+// whatever was planned is out of date. Planners go in PostUpdate, tiles take their new types
+// during Update.
+pub(crate) fn plans_out_of_date(
+    algo: Res<AlgorithmInUse>,
+    mut terrain_gen_reader: EventReader<TerrainGenerationEvent>,
+) -> bool {
+    terrain_gen_reader.read().count() > 0 || algo.is_changed()
+}
+
+// Tile ids from `from_tile` to `goal`, or to the nearest End when there's no goal
+pub(crate) fn plan_path(
+    algo: &Options,
+    tiles: &[&Tile],
+    from_tile: usize,
+    goal: Option<usize>,
+) -> Vec<usize> {
+    let mut tile_grid = TileGrid::from_tiles(tiles);
+    let from = tile_grid.pos(from_tile).unwrap_or((0, 0));
+    if let Some(goal) = goal.and_then(|goal| tile_grid.pos(goal)) {
+        for end in tile_grid.grid.ends() {
+            tile_grid.grid.set_tile_type(end, TileType::Open);
+        }
+        tile_grid.grid.set_tile_type(goal, TileType::End);
     }
+    // Walking starts where the player is, wherever the Start tile might be
+    let options = Options {
+        use_start_tile: false,
+        ..algo.clone()
+    };
+    pathfind(&tile_grid.grid, from, &options)
+        .path
+        .into_iter()
+        .map(|pos| tile_grid.id(pos))
+        .collect()
 }
+
 fn precalc_on_terrain_generation(
     algo: Res<AlgorithmInUse>,
     tiles: Query<&Tile>,
     mut terrain_gen_reader: EventReader<TerrainGenerationEvent>,
     mut precalc: ResMut<Precalc>,
+    mut replanner: ResMut<Replanner>,
 ) {
     for _event in terrain_gen_reader.read() {
         let tiles: Vec<&Tile> = tiles.iter().collect();
-        let (visited, path) = run_algo(&*algo, &tiles, precalc.current_tile);
-        precalc.visited = visited;
-        precalc.path = path;
+        recalc(&algo, &tiles, &mut precalc, &mut replanner);
     }
 }
 
@@ -103,13 +262,12 @@ fn precalc_on_current_tile(
     tiles: Query<&Tile>,
     mut current_tile_reader: EventReader<CurrentTileEvent>,
     mut precalc: ResMut<Precalc>,
+    mut replanner: ResMut<Replanner>,
 ) {
     for event in current_tile_reader.read() {
         let tiles: Vec<&Tile> = tiles.iter().collect();
         precalc.current_tile = event.id;
-        let (visited, path) = run_algo(&*algo, &tiles, precalc.current_tile);
-        precalc.visited = visited;
-        precalc.path = path;
+        recalc(&algo, &tiles, &mut precalc, &mut replanner);
     }
 }
 
@@ -117,33 +275,45 @@ fn precalc_on_algo_change(
     algo: Res<AlgorithmInUse>,
     tiles: Query<&Tile>,
     mut precalc: ResMut<Precalc>,
+    mut replanner: ResMut<Replanner>,
 ) {
     if algo.is_changed() {
         let tiles: Vec<&Tile> = tiles.iter().collect();
-        let (visited, path) = run_algo(&*algo, &tiles, precalc.current_tile);
-        precalc.visited = visited;
-        precalc.path = path;
+        recalc(&algo, &tiles, &mut precalc, &mut replanner);
+    }
+}
+
+fn use_seed_for_random_direction(seed: Res<Seed>, mut algo: ResMut<AlgorithmInUse>) {
+    if seed.is_changed() {
+        algo.seed = **seed;
     }
 }
 
 fn emit_pathfinding_by_button(
     precalc: Res<Precalc>,
+    comparison: Res<Comparison>,
     mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
     mut pathfinding_writer: EventWriter<PathfindingEvent>,
     mut path_writer: EventWriter<PathEvent>,
 ) {
     for input in keyboard_input_reader.read() {
+        // J and H run every compared algorithm instead, over in compare
+        if comparison.enabled {
+            continue;
+        }
         if input.action == InputAction::Pressed {
             match input.key {
                 KeyCode::KeyJ => {
                     pathfinding_writer.send(PathfindingEvent {
                         visited: precalc.visited.clone(),
+                        hue: None,
                     });
                 }
                 KeyCode::KeyH => {
-                    let mut nodes = precalc.path.clone();
-                    nodes.reverse();
-                    path_writer.send(PathEvent { nodes });
+                    path_writer.send(PathEvent {
+                        nodes: precalc.path.clone(),
+                        hue: None,
+                    });
                 }
                 _ => {}
             }
@@ -152,21 +322,27 @@ fn emit_pathfinding_by_button(
 }
 
 fn set_algorithm_from_key_input(
+    keys: Res<ButtonInput<KeyCode>>,
     mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
     mut algo: ResMut<AlgorithmInUse>,
 ) {
     for event in keyboard_input_reader.read() {
-        if event.action == InputAction::Pressed {
+        if event.action == InputAction::Pressed && !shift_held(&keys) {
             match event.key {
-                KeyCode::Digit1 => algo.name = Algorithm::Dijkstra,
-                KeyCode::Digit2 => algo.name = Algorithm::AStar,
-                KeyCode::Digit3 => algo.name = Algorithm::AggressiveStar,
-                KeyCode::Digit4 => algo.name = Algorithm::DFS,
-                KeyCode::Digit5 => algo.name = Algorithm::BFS,
+                KeyCode::Digit1 => algo.algorithm = Algorithm::Dijkstra,
+                KeyCode::Digit2 => algo.algorithm = Algorithm::AStar,
+                KeyCode::Digit3 => algo.algorithm = Algorithm::AggressiveStar,
+                KeyCode::Digit4 => algo.algorithm = Algorithm::DFS,
+                KeyCode::Digit5 => algo.algorithm = Algorithm::BFS,
+                KeyCode::Digit6 => algo.algorithm = Algorithm::JumpPointSearch,
+                KeyCode::Digit7 => algo.algorithm = Algorithm::DStarLite,
                 KeyCode::KeyQ => algo.direction_offset = (algo.direction_offset + 1) % 8,
                 KeyCode::KeyT => algo.direction_offset = (algo.direction_offset + 7) % 8,
                 KeyCode::KeyP => algo.world_wrap_enabled = !algo.world_wrap_enabled,
                 KeyCode::KeyX => algo.random_direction = !algo.random_direction,
+                KeyCode::KeyB => algo.bidirectional = !algo.bidirectional,
+                KeyCode::KeyO => algo.visit_all_goals = !algo.visit_all_goals,
+                KeyCode::Quote => algo.use_start_tile = !algo.use_start_tile,
                 _ => {}
             }
         }
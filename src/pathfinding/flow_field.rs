@@ -0,0 +1,232 @@
+use bevy::prelude::*;
+use pathfinder::pathfinding::algorithms::flow_field::flow_field;
+use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+use crate::input::{InputAction, KeyboardInputEvent};
+use crate::{
+    entities::{
+        ground::{
+            GROUND_B_BORDER, GROUND_H, GROUND_L_BORDER, GROUND_R_BORDER, GROUND_T_BORDER, GROUND_W,
+        },
+        tile::{tile_center, tile_under, Tile, TileGrid, TILE_OFFSET, TILE_SIZE},
+    },
+    pathfinding::emit_pathfinding::{plans_out_of_date, AlgorithmInUse},
+    seed::Seed,
+};
+
+const DEFAULT_SWARM_SIZE: usize = 50;
+const SWARM_SIZE_STEP: usize = 10;
+const MAX_SWARM_SIZE: usize = 500;
+const AGENT_SPEED: f32 = 150.;
+const AGENT_RADIUS: f32 = 6.;
+const AGENT_COLOR: Color = Color::hsl(50., 0.90, 0.60);
+// Arrows are drawn centered on their tile, this much of a tile long
+const FIELD_ARROW_LENGTH: f32 = 0.5;
+const FIELD_ARROW_COLOR: Color = Color::srgba(1., 1., 1., 0.35);
+
+pub struct FlowFieldPlugin;
+
+impl Plugin for FlowFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FlowField {
+            enabled: false,
+            swarm_size: DEFAULT_SWARM_SIZE,
+            directions: vec![],
+            compute_time: Duration::ZERO,
+            stale: true,
+            rng: StdRng::seed_from_u64(0),
+        })
+        .add_systems(
+            Update,
+            (
+                set_flow_field_from_key_input,
+                (use_seed_for_swarm, fill_swarm, follow_flow_field).chain(),
+                draw_flow_field,
+            ),
+        )
+        // World wrap changes which way is shortest too, so any algorithm change redoes it
+        .add_systems(
+            PostUpdate,
+            recompute_flow_field.run_if(plans_out_of_date.or(|flow: Res<FlowField>| flow.stale)),
+        );
+    }
+}
+
+/*
+ * One integration field from the End, shared by the whole swarm. However many agents there are
+ * it costs a single Dijkstra over the grid, next to one A* each. Agents that reach the End (or
+ * get walled off) start over somewhere else so the swarm keeps its size.
+ */
+#[derive(Resource)]
+pub struct FlowField {
+    pub enabled: bool,
+    pub swarm_size: usize,
+    // Indexed [row][col], the step toward the End or None when there isn't one
+    directions: Vec<Vec<Option<(isize, isize)>>>,
+    pub compute_time: Duration,
+    stale: bool,
+    // Where agents (re)spawn, the same seed puts them in the same places
+    rng: StdRng,
+}
+
+#[derive(Component)]
+struct SwarmAgent;
+
+fn set_flow_field_from_key_input(
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+    mut flow: ResMut<FlowField>,
+) {
+    for event in keyboard_input_reader.read() {
+        if event.action != InputAction::Pressed {
+            continue;
+        }
+        match event.key {
+            KeyCode::F8 => {
+                flow.enabled = !flow.enabled;
+                flow.stale = true;
+            }
+            KeyCode::F9 => flow.swarm_size = flow.swarm_size.saturating_sub(SWARM_SIZE_STEP),
+            KeyCode::F10 => {
+                flow.swarm_size = (flow.swarm_size + SWARM_SIZE_STEP).min(MAX_SWARM_SIZE)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn use_seed_for_swarm(seed: Res<Seed>, mut flow: ResMut<FlowField>) {
+    if seed.is_changed() {
+        flow.rng = StdRng::seed_from_u64(**seed);
+    }
+}
+
+fn recompute_flow_field(
+    algo: Res<AlgorithmInUse>,
+    tiles: Query<&Tile>,
+    mut flow: ResMut<FlowField>,
+) {
+    if !flow.enabled {
+        return;
+    }
+    let tiles: Vec<&Tile> = tiles.iter().collect();
+    let tile_grid = TileGrid::from_tiles(&tiles);
+    let started = Instant::now();
+    flow.directions = flow_field(&tile_grid.grid, algo.world_wrap_enabled);
+    flow.compute_time = started.elapsed();
+    flow.stale = false;
+}
+
+// Anywhere with an arrow to follow, a little off center so they don't stack up
+fn random_start(directions: &[Vec<Option<(isize, isize)>>], rng: &mut StdRng) -> Option<Vec2> {
+    let pos = directions
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cols)| {
+            cols.iter()
+                .enumerate()
+                .filter(|(_, direction)| direction.is_some())
+                .map(move |(col, _)| (row, col))
+        })
+        .choose(rng)?;
+    let jitter = TILE_OFFSET - AGENT_RADIUS;
+    Some(
+        tile_center(pos)
+            + Vec2::new(
+                rng.gen_range(-jitter..jitter),
+                rng.gen_range(-jitter..jitter),
+            ),
+    )
+}
+
+fn fill_swarm(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut flow: ResMut<FlowField>,
+    agents: Query<Entity, With<SwarmAgent>>,
+) {
+    let flow = &mut *flow;
+    let wanted = if flow.enabled { flow.swarm_size } else { 0 };
+    let count = agents.iter().count();
+    for agent in agents.iter().skip(wanted) {
+        commands.entity(agent).despawn();
+    }
+    if count >= wanted {
+        return;
+    }
+
+    let mesh = meshes.add(Circle::new(AGENT_RADIUS));
+    let material = materials.add(AGENT_COLOR);
+    for _ in count..wanted {
+        let Some(start) = random_start(&flow.directions, &mut flow.rng) else {
+            return;
+        };
+        commands.spawn((
+            SwarmAgent,
+            Mesh2d(mesh.clone()),
+            MeshMaterial2d(material.clone()),
+            Transform::from_xyz(start.x, start.y, 0.9),
+        ));
+    }
+}
+
+// Head for the middle of whichever tile the arrow underfoot points at
+fn follow_flow_field(
+    time: Res<Time>,
+    mut flow: ResMut<FlowField>,
+    mut agents: Query<&mut Transform, With<SwarmAgent>>,
+) {
+    let flow = &mut *flow;
+    if flow.directions.is_empty() {
+        return;
+    }
+    for mut xf in &mut agents {
+        let position = xf.translation.truncate();
+        let (row, col) = tile_under(position);
+        let Some((dr, dc)) = flow.directions[row][col] else {
+            // Made it, or got walled in, either way go again
+            if let Some(start) = random_start(&flow.directions, &mut flow.rng) {
+                xf.translation.x = start.x;
+                xf.translation.y = start.y;
+            }
+            continue;
+        };
+
+        // Past the edge is fine, it gets wrapped back around below
+        let target = tile_center((row, col)) + Vec2::new(dc as f32, -dr as f32) * TILE_SIZE;
+        let heading = (target - position).normalize_or_zero();
+        xf.translation.x += heading.x * AGENT_SPEED * time.delta_secs();
+        xf.translation.y += heading.y * AGENT_SPEED * time.delta_secs();
+
+        if xf.translation.x > GROUND_R_BORDER {
+            xf.translation.x -= GROUND_W;
+        }
+        if xf.translation.x < GROUND_L_BORDER {
+            xf.translation.x += GROUND_W;
+        }
+        if xf.translation.y < GROUND_B_BORDER {
+            xf.translation.y += GROUND_H;
+        }
+        if xf.translation.y > GROUND_T_BORDER {
+            xf.translation.y -= GROUND_H;
+        }
+    }
+}
+
+fn draw_flow_field(mut gizmos: Gizmos, flow: Res<FlowField>) {
+    if !flow.enabled {
+        return;
+    }
+    for (row, cols) in flow.directions.iter().enumerate() {
+        for (col, direction) in cols.iter().enumerate() {
+            let Some((dr, dc)) = *direction else {
+                continue;
+            };
+            let center = tile_center((row, col));
+            let half =
+                Vec2::new(dc as f32, -dr as f32).normalize() * TILE_SIZE * FIELD_ARROW_LENGTH / 2.;
+            gizmos.arrow_2d(center - half, center + half, FIELD_ARROW_COLOR);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::input::{InputAction, KeyboardCaptured, KeyboardInputEvent};
+
+// Small enough to read off the bottom-left corner and type back in
+const MAX_ROLLED_SEED: u64 = 1_000_000;
+
+pub struct SeedPlugin;
+
+impl Plugin for SeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Seed(thread_rng().gen_range(0..MAX_ROLLED_SEED)))
+            .insert_resource(SeedEntry::default())
+            .add_systems(Startup, spawn_seed_text)
+            .add_systems(Update, (reroll_seed, enter_seed, update_seed_text).chain());
+    }
+}
+
+// Everything random (mazes, the random direction bias) starts from this, same seed same result
+#[derive(Resource, Deref, DerefMut, Clone, Copy, PartialEq, Debug)]
+pub struct Seed(pub u64);
+
+// Digits typed so far, None when not typing a seed
+#[derive(Resource, Default)]
+struct SeedEntry(Option<String>);
+
+fn reroll_seed(mut seed: ResMut<Seed>, mut keyboard_input_reader: EventReader<KeyboardInputEvent>) {
+    for input in keyboard_input_reader.read() {
+        if input.action == InputAction::Pressed && input.key == KeyCode::KeyU {
+            **seed = thread_rng().gen_range(0..MAX_ROLLED_SEED);
+        }
+    }
+}
+
+/*
+ * I starts typing, digits go into the entry instead of picking algorithms, Enter sets it and
+ * Escape gives up. The keyboard is captured the whole time so nothing else hears the digits.
+ */
+fn enter_seed(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut seed: ResMut<Seed>,
+    mut entry: ResMut<SeedEntry>,
+    mut captured: ResMut<KeyboardCaptured>,
+    mut keyboard_input_reader: EventReader<KeyboardInputEvent>,
+) {
+    if let Some(digits) = &mut entry.0 {
+        for key in keys.get_just_pressed() {
+            match key {
+                KeyCode::Enter | KeyCode::NumpadEnter => {
+                    // Too many digits for a u64 just keeps the old seed
+                    if let Ok(entered) = digits.parse() {
+                        **seed = entered;
+                    }
+                    entry.0 = None;
+                    break;
+                }
+                KeyCode::Escape => {
+                    entry.0 = None;
+                    break;
+                }
+                KeyCode::Backspace => {
+                    digits.pop();
+                }
+                _ => {
+                    if let Some(digit) = key_digit(*key) {
+                        digits.push(digit);
+                    }
+                }
+            }
+        }
+    } else {
+        for input in keyboard_input_reader.read() {
+            if input.action == InputAction::Pressed && input.key == KeyCode::KeyI {
+                entry.0 = Some(String::new());
+            }
+        }
+    }
+    keyboard_input_reader.clear();
+
+    if captured.0 != entry.0.is_some() {
+        captured.0 = entry.0.is_some();
+    }
+}
+
+fn key_digit(key: KeyCode) -> Option<char> {
+    match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => Some('0'),
+        KeyCode::Digit1 | KeyCode::Numpad1 => Some('1'),
+        KeyCode::Digit2 | KeyCode::Numpad2 => Some('2'),
+        KeyCode::Digit3 | KeyCode::Numpad3 => Some('3'),
+        KeyCode::Digit4 | KeyCode::Numpad4 => Some('4'),
+        KeyCode::Digit5 | KeyCode::Numpad5 => Some('5'),
+        KeyCode::Digit6 | KeyCode::Numpad6 => Some('6'),
+        KeyCode::Digit7 | KeyCode::Numpad7 => Some('7'),
+        KeyCode::Digit8 | KeyCode::Numpad8 => Some('8'),
+        KeyCode::Digit9 | KeyCode::Numpad9 => Some('9'),
+        _ => None,
+    }
+}
+
+#[derive(Component)]
+struct SeedText;
+
+fn spawn_seed_text(mut commands: Commands) {
+    commands.spawn((
+        SeedText,
+        Text::default(),
+        TextFont {
+            font_size: 24.,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.),
+            left: Val::Px(12.),
+            ..default()
+        },
+    ));
+}
+
+fn update_seed_text(
+    seed: Res<Seed>,
+    entry: Res<SeedEntry>,
+    mut q_text: Query<&mut Text, With<SeedText>>,
+) {
+    let text = match &entry.0 {
+        Some(digits) => format!("Enter seed: {}_", digits),
+        None => format!("Seed: {}", **seed),
+    };
+    for mut seed_text in &mut q_text {
+        if seed_text.0 != text {
+            seed_text.0 = text.clone();
+        }
+    }
+}
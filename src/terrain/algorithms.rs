@@ -1,10 +0,0 @@
-use bevy::prelude::*;
-pub mod node;
-pub mod wilsons;
-pub mod wilsons_bounded;
-
-#[derive(Resource, PartialEq)]
-pub enum TerrainAlgorithm {
-    Wilsons,
-    WilsonsBounded,
-}
@@ -1,15 +1,22 @@
 use bevy::prelude::*;
+use pathfinder::{generate_maze, parse_map, write_map, Map, TerrainAlgorithm, TerrainSettings};
+use std::fs;
 
-use super::algorithms::{
-    wilsons::setup_and_run_wilsons, wilsons_bounded::setup_and_run_wilsons_bounded,
-    TerrainAlgorithm,
-};
-use crate::input::{InputAction, KeyboardInputEvent, MouseInputEvent};
+use crate::input::{shift_held, InputAction, KeyboardInputEvent, MouseInputEvent};
 use crate::{
-    current_tile::emitter::CurrentMouseTileEvent,
-    entities::tile::{Tile, TileType, COL_COUNT, ROW_COUNT},
+    current_tile::emitter::{CurrentMouseTileEvent, CurrentTileEvent},
+    entities::{
+        player::{click_to_move::ClickToMove, movement::PlayerTeleportEvent},
+        tile::{Tile, TileGrid, TileType, COL_COUNT, ROW_COUNT},
+    },
+    seed::Seed,
 };
 
+// Lives next to wherever you ran it from, open it up in an editor to draw by hand
+const MAP_FILE_PATH: &str = "paint-by-algo.map";
+
+pub use pathfinder::terrain::algorithms::TerrainAction;
+
 #[derive(Clone)]
 pub struct TerrainNode {
     pub tile_id: usize,
@@ -22,25 +29,72 @@ pub struct TerrainGenerationEvent {
     pub terrain_events: Vec<TerrainNode>,
 }
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum TerrainAction {
-    Added,
-    Removed,
-}
-
 #[derive(Resource, Copy, Clone, Debug, PartialEq)]
 pub enum BuildType {
     Wall,
     End,
+    Start,
+    Road,
+    Mud,
+    Water,
+}
+
+impl BuildType {
+    // Walkable tiles that cost something different to cross
+    pub fn is_weighted(&self) -> bool {
+        matches!(self, BuildType::Road | BuildType::Mud | BuildType::Water)
+    }
+}
+
+// Only tiles you can build have a build type, Open is what's left after a removal
+impl TryFrom<TileType> for BuildType {
+    type Error = ();
+
+    fn try_from(tile_type: TileType) -> Result<Self, Self::Error> {
+        match tile_type {
+            TileType::Wall => Ok(BuildType::Wall),
+            TileType::End => Ok(BuildType::End),
+            TileType::Start => Ok(BuildType::Start),
+            TileType::Road => Ok(BuildType::Road),
+            TileType::Mud => Ok(BuildType::Mud),
+            TileType::Water => Ok(BuildType::Water),
+            TileType::Open => Err(()),
+        }
+    }
 }
 
+impl From<BuildType> for TileType {
+    fn from(build_type: BuildType) -> Self {
+        match build_type {
+            BuildType::Wall => TileType::Wall,
+            BuildType::End => TileType::End,
+            BuildType::Start => TileType::Start,
+            BuildType::Road => TileType::Road,
+            BuildType::Mud => TileType::Mud,
+            BuildType::Water => TileType::Water,
+        }
+    }
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct TerrainAlgorithmInUse(pub TerrainAlgorithm);
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct TerrainSettingsInUse(pub TerrainSettings);
+
+// How the last save or load went, for the HUD
+#[derive(Resource, Deref, DerefMut)]
+pub struct MapFileStatus(pub String);
+
 pub struct TileModifierPlugin;
 
 impl Plugin for TileModifierPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<TerrainGenerationEvent>()
             .insert_resource(BuildType::Wall)
-            .insert_resource(TerrainAlgorithm::WilsonsBounded)
+            .insert_resource(TerrainAlgorithmInUse(TerrainAlgorithm::WilsonsBounded))
+            .insert_resource(TerrainSettingsInUse(TerrainSettings::default()))
+            .insert_resource(MapFileStatus(format!("K saves, L loads {}", MAP_FILE_PATH)))
             .add_systems(
                 Update,
                 (
@@ -50,6 +104,9 @@ impl Plugin for TileModifierPlugin {
                     manage_build_type,
                     build_walls_to_block_world_wrap,
                     set_algorithm_from_key_input,
+                    set_terrain_settings_from_key_input,
+                    save_map,
+                    load_map,
                 ),
             );
     }
@@ -68,6 +125,22 @@ fn manage_build_type(
             if event.key == KeyCode::KeyR {
                 *build_type = BuildType::Wall;
             }
+
+            if event.key == KeyCode::KeyG {
+                *build_type = BuildType::Road;
+            }
+
+            if event.key == KeyCode::KeyV {
+                *build_type = BuildType::Mud;
+            }
+
+            if event.key == KeyCode::KeyY {
+                *build_type = BuildType::Water;
+            }
+
+            if event.key == KeyCode::Semicolon {
+                *build_type = BuildType::Start;
+            }
         }
     }
 }
@@ -142,38 +215,203 @@ fn build_maze_with_algorithm(
     q_tiles: Query<&Tile>,
     mut player_input_reader: EventReader<KeyboardInputEvent>,
     mut maze_gen_writer: EventWriter<TerrainGenerationEvent>,
-    algo: Res<TerrainAlgorithm>,
+    algo: Res<TerrainAlgorithmInUse>,
+    settings: Res<TerrainSettingsInUse>,
+    seed: Res<Seed>,
 ) {
     for input in player_input_reader.read() {
         if input.action == InputAction::Pressed && input.key == KeyCode::KeyN {
             let tiles: Vec<&Tile> = q_tiles.iter().collect();
-            let events = match *algo {
-                TerrainAlgorithm::Wilsons => setup_and_run_wilsons(&tiles),
-                TerrainAlgorithm::WilsonsBounded => setup_and_run_wilsons_bounded(&tiles),
+            let mut tile_grid = TileGrid::from_tiles(&tiles);
+            let passes = generate_maze(&mut tile_grid.grid, **algo, &settings, **seed);
+            for steps in passes {
+                let events = steps
+                    .into_iter()
+                    .filter_map(|step| {
+                        BuildType::try_from(step.tile_type)
+                            .ok()
+                            .map(|build_type| TerrainNode {
+                                tile_id: tile_grid.id(step.pos),
+                                build_type,
+                                action: step.action,
+                            })
+                    })
+                    .collect();
+                maze_gen_writer.send(TerrainGenerationEvent {
+                    terrain_events: events,
+                });
+            }
+        }
+    }
+}
+
+fn save_map(
+    q_tiles: Query<&Tile>,
+    mut player_input_reader: EventReader<KeyboardInputEvent>,
+    mut current_tile_reader: EventReader<CurrentTileEvent>,
+    mut current_tile_id: Local<Option<usize>>,
+    mut status: ResMut<MapFileStatus>,
+) {
+    for event in current_tile_reader.read() {
+        *current_tile_id = Some(event.id);
+    }
+
+    for input in player_input_reader.read() {
+        if input.action == InputAction::Pressed && input.key == KeyCode::KeyK {
+            let tiles: Vec<&Tile> = q_tiles.iter().collect();
+            let tile_grid = TileGrid::from_tiles(&tiles);
+            let map = Map {
+                start: current_tile_id.and_then(|id| tile_grid.pos(id)),
+                grid: tile_grid.grid,
+            };
+            **status = match fs::write(MAP_FILE_PATH, write_map(&map)) {
+                Ok(()) => format!("saved to {}", MAP_FILE_PATH),
+                Err(err) => format!("couldn't save to {}: {}", MAP_FILE_PATH, err),
             };
-            maze_gen_writer.send(TerrainGenerationEvent {
-                terrain_events: events,
-            });
+        }
+    }
+}
+
+fn load_map(
+    q_tiles: Query<(&Transform, &Tile)>,
+    mut player_input_reader: EventReader<KeyboardInputEvent>,
+    mut terrain_gen_writer: EventWriter<TerrainGenerationEvent>,
+    mut teleport_writer: EventWriter<PlayerTeleportEvent>,
+    mut status: ResMut<MapFileStatus>,
+) {
+    for input in player_input_reader.read() {
+        if input.action == InputAction::Pressed && input.key == KeyCode::KeyL {
+            let map = match fs::read_to_string(MAP_FILE_PATH)
+                .map_err(|err| err.to_string())
+                .and_then(|text| parse_map(&text).map_err(|err| err.to_string()))
+            {
+                Ok(map) => map,
+                Err(err) => {
+                    **status = format!("couldn't load {}: {}", MAP_FILE_PATH, err);
+                    continue;
+                }
+            };
+            if map.grid.rows() != ROW_COUNT || map.grid.cols() != COL_COUNT {
+                **status = format!(
+                    "couldn't load {}: it's {}x{}, the world is {}x{}",
+                    MAP_FILE_PATH,
+                    map.grid.rows(),
+                    map.grid.cols(),
+                    ROW_COUNT,
+                    COL_COUNT
+                );
+                continue;
+            }
+
+            let mut terrain_events = vec![];
+            for (xf, tile) in &q_tiles {
+                if map.start == Some((tile.row, tile.col)) {
+                    teleport_writer.send(PlayerTeleportEvent {
+                        x: xf.translation.x,
+                        y: xf.translation.y,
+                    });
+                }
+
+                let tile_type = map.grid.tile_type((tile.row, tile.col));
+                if tile_type == tile.tile_type {
+                    continue;
+                }
+                if let Ok(build_type) = BuildType::try_from(tile_type) {
+                    terrain_events.push(TerrainNode {
+                        tile_id: tile.id,
+                        build_type,
+                        action: TerrainAction::Added,
+                    });
+                } else if let Ok(build_type) = BuildType::try_from(tile.tile_type) {
+                    terrain_events.push(TerrainNode {
+                        tile_id: tile.id,
+                        build_type,
+                        action: TerrainAction::Removed,
+                    });
+                }
+            }
+            terrain_gen_writer.send(TerrainGenerationEvent { terrain_events });
+            **status = format!("loaded {}", MAP_FILE_PATH);
         }
     }
 }
 
 fn set_algorithm_from_key_input(
-    mut algo: ResMut<TerrainAlgorithm>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut algo: ResMut<TerrainAlgorithmInUse>,
     mut player_input_reader: EventReader<KeyboardInputEvent>,
 ) {
     for event in player_input_reader.read() {
-        if event.action == InputAction::Pressed {
+        if event.action == InputAction::Pressed && shift_held(&keys) {
             if event.key == KeyCode::Digit1 {
-                *algo = TerrainAlgorithm::WilsonsBounded;
+                **algo = TerrainAlgorithm::WilsonsBounded;
             }
 
             if event.key == KeyCode::Digit2 {
-                *algo = TerrainAlgorithm::Wilsons;
+                **algo = TerrainAlgorithm::Wilsons;
             }
 
             if event.key == KeyCode::Digit3 {
-                *algo = TerrainAlgorithm::Wilsons;
+                **algo = TerrainAlgorithm::RecursiveBacktracker;
+            }
+
+            if event.key == KeyCode::Digit4 {
+                **algo = TerrainAlgorithm::PrimsBounded;
+            }
+
+            if event.key == KeyCode::Digit5 {
+                **algo = TerrainAlgorithm::Prims;
+            }
+
+            if event.key == KeyCode::Digit6 {
+                **algo = TerrainAlgorithm::KruskalsBounded;
+            }
+
+            if event.key == KeyCode::Digit7 {
+                **algo = TerrainAlgorithm::Kruskals;
+            }
+
+            if event.key == KeyCode::Digit8 {
+                **algo = TerrainAlgorithm::RecursiveDivision;
+            }
+
+            if event.key == KeyCode::Digit9 {
+                **algo = TerrainAlgorithm::Caves;
+            }
+
+            if event.key == KeyCode::Digit0 {
+                **algo = TerrainAlgorithm::Dungeon;
+            }
+        }
+    }
+}
+
+fn set_terrain_settings_from_key_input(
+    mut settings: ResMut<TerrainSettingsInUse>,
+    mut player_input_reader: EventReader<KeyboardInputEvent>,
+) {
+    for event in player_input_reader.read() {
+        if event.action == InputAction::Pressed {
+            match event.key {
+                KeyCode::BracketLeft => {
+                    settings.cave_fill_ratio = (settings.cave_fill_ratio - 0.05).max(0.);
+                }
+                KeyCode::BracketRight => {
+                    settings.cave_fill_ratio = (settings.cave_fill_ratio + 0.05).min(1.);
+                }
+                KeyCode::Minus => {
+                    settings.cave_iterations = settings.cave_iterations.saturating_sub(1);
+                }
+                KeyCode::Equal => {
+                    settings.cave_iterations += 1;
+                }
+                KeyCode::Comma => {
+                    settings.braid_ratio = (settings.braid_ratio - 0.1).max(0.);
+                }
+                KeyCode::Period => {
+                    settings.braid_ratio = (settings.braid_ratio + 0.1).min(1.);
+                }
+                _ => {}
             }
         }
     }
@@ -182,6 +420,7 @@ fn set_algorithm_from_key_input(
 fn manage_wall_placement_from_mouse_input(
     q_tiles: Query<&Tile>,
     build_state: Res<BuildType>,
+    click_to_move: Res<ClickToMove>,
     mut current_mouse_tile_reader: EventReader<CurrentMouseTileEvent>,
     mut mouse_input_reader: EventReader<MouseInputEvent>,
     mut terrain_gen_writer: EventWriter<TerrainGenerationEvent>,
@@ -211,19 +450,35 @@ fn manage_wall_placement_from_mouse_input(
     if let Some(current_tile) = *current_tile_id {
         if *left_pressed {
             for tile in &q_tiles {
-                if tile.id == current_tile && tile.tile_type != TileType::Wall {
-                    terrain_gen_writer.send(TerrainGenerationEvent {
-                        terrain_events: vec![TerrainNode {
-                            tile_id: tile.id,
-                            build_type: *build_state,
-                            action: TerrainAction::Added,
-                        }],
+                if tile.id == current_tile
+                    && tile.tile_type != TileType::Wall
+                    && tile.tile_type != TileType::from(*build_state)
+                {
+                    let mut terrain_events = vec![];
+                    // Only one Start, the old one goes when a new one is placed
+                    if *build_state == BuildType::Start {
+                        for old_start in &q_tiles {
+                            if old_start.tile_type == TileType::Start {
+                                terrain_events.push(TerrainNode {
+                                    tile_id: old_start.id,
+                                    build_type: BuildType::Start,
+                                    action: TerrainAction::Removed,
+                                });
+                            }
+                        }
+                    }
+                    terrain_events.push(TerrainNode {
+                        tile_id: tile.id,
+                        build_type: *build_state,
+                        action: TerrainAction::Added,
                     });
+                    terrain_gen_writer.send(TerrainGenerationEvent { terrain_events });
                 }
             }
         }
 
-        if *right_pressed {
+        // Right-click is picking where to walk instead
+        if *right_pressed && !click_to_move.enabled {
             for tile in &q_tiles {
                 if tile.id == current_tile {
                     terrain_gen_writer.send(TerrainGenerationEvent {